@@ -0,0 +1,38 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A source of the current time, used by [`Client::now_millis`] for everything the client
+/// derives from the time, such as the timestamps of signed requests.
+///
+/// By default the [`Client`] uses the [`SystemClock`], but it can be replaced using
+/// [`Client::with_time_source`], for instance to pin the time in tests. Any closure returning
+/// the time in milliseconds is a time source.
+///
+/// [`Client`]: crate::Client
+/// [`Client::now_millis`]: crate::Client::now_millis
+/// [`Client::with_time_source`]: crate::Client::with_time_source
+pub trait TimeSource: Send + Sync {
+    /// The current time, in milliseconds since the Unix epoch.
+    fn now_millis(&self) -> u64;
+}
+
+/// The system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64
+    }
+}
+
+impl<F> TimeSource for F
+where
+    F: Fn() -> u64 + Send + Sync,
+{
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
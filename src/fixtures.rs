@@ -0,0 +1,71 @@
+//! JSON fixtures shared by the tests.
+//!
+//! Each fixture is one of the example responses in `src/testing`, with the fields given in
+//! `overrides` replaced, so a test only spells out what it depends on.
+
+use serde_json::Value;
+
+fn load(fixture: &str, overrides: Value) -> Value {
+    let value = serde_json::from_str(fixture).expect("The fixture should be valid JSON");
+    with_overrides(value, overrides)
+}
+
+fn with_overrides(mut value: Value, overrides: Value) -> Value {
+    if let Value::Object(overrides) = overrides {
+        for (field, v) in overrides {
+            value[field] = v;
+        }
+    }
+    value
+}
+
+pub fn asset(overrides: Value) -> Value {
+    load(include_str!("testing/asset.json"), overrides)
+}
+
+pub fn market(overrides: Value) -> Value {
+    load(include_str!("testing/market.json"), overrides)
+}
+
+#[cfg(feature = "decimal")]
+pub fn ticker_24h(overrides: Value) -> Value {
+    load(include_str!("testing/ticker_24h.json"), overrides)
+}
+
+#[cfg(feature = "client")]
+pub fn ticker_book(overrides: Value) -> Value {
+    load(include_str!("testing/ticker_book.json"), overrides)
+}
+
+pub fn order(overrides: Value) -> Value {
+    load(include_str!("testing/order.json"), overrides)
+}
+
+/// A public trade of the BTC-EUR market. There is no example response for trades.
+#[cfg(feature = "client")]
+pub fn trade(overrides: Value) -> Value {
+    let trade = serde_json::json!({
+        "id": "57b1159b-6bf5-4cde-9e2c-6bd6a5678baf",
+        "timestamp": 1542967486256u64,
+        "amount": "0.1",
+        "price": "30000",
+        "side": "buy",
+    });
+    with_overrides(trade, overrides)
+}
+
+/// A deposit or withdrawal of BTC, which share their fields. There is no example response for
+/// either.
+#[cfg(feature = "client")]
+pub fn transfer(overrides: Value) -> Value {
+    let transfer = serde_json::json!({
+        "timestamp": 1542967486256u64,
+        "symbol": "BTC",
+        "amount": "0.1",
+        "address": "BitcoinAddress",
+        "txId": "0xabc",
+        "fee": "0.0001",
+        "status": "completed",
+    });
+    with_overrides(transfer, overrides)
+}
@@ -1,37 +1,172 @@
+#[cfg(feature = "client")]
+pub mod clock;
+#[cfg(test)]
+mod fixtures;
+pub mod prelude;
+#[cfg(feature = "client")]
+pub mod recorder;
+#[cfg(feature = "client")]
+pub mod signer;
+#[cfg(feature = "client")]
+mod single_flight;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "client")]
+pub mod transport;
 pub mod types;
 
 use std::error::Error as StdError;
 use std::fmt;
-use std::time::{SystemTime, UNIX_EPOCH};
 
-use hmac::Mac;
-use reqwest::Response;
+#[cfg(feature = "client")]
+use std::collections::{BTreeSet, HashMap};
+#[cfg(feature = "client")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "client")]
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "client")]
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+#[cfg(feature = "client")]
+use futures_util::stream::{self, StreamExt};
+#[cfg(feature = "client")]
+use reqwest::{Response, Url};
+#[cfg(feature = "client")]
 use serde::de::DeserializeOwned;
+#[cfg(feature = "client")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "client")]
+use uuid::Uuid;
+#[cfg(feature = "client")]
 use zeroize::Zeroizing;
 
+#[cfg(feature = "client")]
+use clock::{SystemClock, TimeSource};
+#[cfg(feature = "client")]
+use signer::{HmacSha256Signer, Signer};
+#[cfg(feature = "client")]
+use single_flight::SingleFlight;
+#[cfg(feature = "client")]
+use transport::Transport;
+#[cfg(feature = "client")]
 use types::*;
 
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
 /// Error type returned by the API.
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
-    Reqwest(reqwest::Error),
+    #[cfg(feature = "client")]
+    Reqwest {
+        endpoint: String,
+        source: reqwest::Error,
+    },
     Serde(serde_json::Error),
-    Bitvavo { code: u64, message: String },
+    Bitvavo {
+        code: u64,
+        message: String,
+    },
+    /// The API rejected the signature of a request, with error code `308` or `309`.
+    SignatureRejected {
+        code: u64,
+        message: String,
+    },
+    /// The API rejected the timestamp of a request, with error code `302` or `304`.
+    ///
+    /// This usually means the local clock is off from the server's.
+    TimestampOutOfRange {
+        code: u64,
+        message: String,
+    },
+    #[cfg(feature = "client")]
     InvalidSecret(BadSecret),
+    InvalidOrder(InvalidOrder),
+    InvalidNumber {
+        field: &'static str,
+        value: String,
+    },
+    NonceMismatch {
+        expected: u64,
+        received: u64,
+    },
+    /// The body of a response was larger than the configured maximum, and was not read in full.
+    ResponseTooLarge {
+        endpoint: String,
+        limit: usize,
+    },
+    /// The client is not set up for the operation, such as calling an account endpoint on a
+    /// client without credentials. The reason says what is missing.
+    Unsupported(&'static str),
+    /// The API responded with a redirect, to the given location if any. Redirects are never
+    /// followed, so signed requests are only ever sent to the configured base URL.
+    UnexpectedRedirect {
+        location: Option<String>,
+    },
+    /// Markets asked for by name that the API does not list.
+    UnknownMarkets(Vec<String>),
+    /// A date whose bounds cannot be represented as timestamps, such as one before 1970.
+    #[cfg(feature = "chrono-tz")]
+    DateOutOfRange(chrono::NaiveDate),
+    Multiple(Vec<Error>),
+    /// An error of a request that was shared by several concurrent calls, such as those of
+    /// [`Client::markets`], each of which gets a reference to it. It otherwise behaves as the
+    /// error it wraps.
+    #[cfg(feature = "client")]
+    Shared(Arc<Error>),
 }
 
 /// Error type for a bad secret.
+#[cfg(feature = "client")]
 #[derive(Debug)]
 pub enum BadSecret {
     InvalidLength(hmac::digest::InvalidLength),
+    /// The secret is not a hex string, as checked by [`Client::try_with_credentials`]. Secrets
+    /// are never hex decoded for signing.
     Hex(hex::FromHexError),
+    /// The API key is empty.
+    EmptyKey,
+    /// The API secret is empty.
+    EmptySecret,
+}
+
+/// Error type for an order that is invalid, as found by [`Order::validate`], or that cannot be
+/// built from the data given.
+///
+/// [`Order::validate`]: types::Order::validate
+#[derive(Debug)]
+pub enum InvalidOrder {
+    /// The order sets neither `amount` nor `amountQuote`.
+    MissingAmount,
+    /// The order sets both `amount` and `amountQuote`.
+    BothAmounts,
+    /// A market buy sets `amount` instead of `amountQuote`.
+    AmountOnMarketBuy,
+    /// A market sell sets `amountQuote` instead of `amount`.
+    AmountQuoteOnMarketSell,
+    /// The order is priced from the order book of a different market.
+    BookMarketMismatch { market: String, book: String },
 }
 
-impl From<reqwest::Error> for Error {
-    fn from(err: reqwest::Error) -> Self {
-        Self::Reqwest(err)
+impl fmt::Display for InvalidOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidOrder::MissingAmount => write!(f, "neither amount nor amountQuote is set"),
+            InvalidOrder::BothAmounts => write!(f, "both amount and amountQuote are set"),
+            InvalidOrder::AmountOnMarketBuy => {
+                write!(f, "a market buy must set amountQuote, not amount")
+            }
+            InvalidOrder::AmountQuoteOnMarketSell => {
+                write!(f, "a market sell must set amount, not amountQuote")
+            }
+            InvalidOrder::BookMarketMismatch { market, book } => {
+                write!(
+                    f,
+                    "the order is for {market}, but the order book is for {book}"
+                )
+            }
+        }
     }
 }
 
@@ -41,19 +176,61 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "client")]
 impl From<hmac::digest::InvalidLength> for Error {
     fn from(err: hmac::digest::InvalidLength) -> Self {
         Self::InvalidSecret(BadSecret::InvalidLength(err))
     }
 }
 
+#[cfg(feature = "client")]
 impl From<hex::FromHexError> for Error {
     fn from(err: hex::FromHexError) -> Self {
         Self::InvalidSecret(BadSecret::Hex(err))
     }
 }
 
-async fn response_from_request<T: DeserializeOwned>(rsp: Response) -> Result<T, Error> {
+#[cfg(feature = "client")]
+async fn response_from_request<T: DeserializeOwned>(rsp: BoundedResponse) -> Result<T, Error> {
+    let bytes = success_bytes(rsp).await?;
+
+    // An empty body is treated as `null`, so it decodes into `()` or `None`.
+    let bytes = bytes.as_ref();
+    if bytes.is_empty() {
+        return Ok(serde_json::from_slice(b"null")?);
+    }
+
+    decode(bytes)
+}
+
+/// Decode a JSON response body.
+#[cfg(all(feature = "client", not(feature = "simd-json")))]
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    Ok(serde_json::from_slice(bytes)?)
+}
+
+/// Decode a JSON response body using simd-json.
+///
+/// simd-json parses in place, so the body is copied first. Its errors are reported as
+/// [`Error::Serde`], so the error type does not depend on the feature.
+#[cfg(all(feature = "client", feature = "simd-json"))]
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let mut bytes = bytes.to_vec();
+    simd_json::serde::from_slice(&mut bytes)
+        .map_err(|err| Error::Serde(serde::de::Error::custom(err)))
+}
+
+/// Check a response for success, ignoring its body. This is for endpoints whose response carries
+/// no content of interest, and may be empty.
+#[cfg(feature = "client")]
+async fn response_unit(rsp: BoundedResponse) -> Result<(), Error> {
+    success_bytes(rsp).await?;
+    Ok(())
+}
+
+/// Read the body of a response, turning an unsuccessful status into the error it carries.
+#[cfg(feature = "client")]
+async fn success_bytes(rsp: BoundedResponse) -> Result<impl AsRef<[u8]>, Error> {
     #[derive(Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
     struct BitvavoError {
@@ -61,592 +238,1431 @@ async fn response_from_request<T: DeserializeOwned>(rsp: Response) -> Result<T,
         error: String,
     }
 
-    let status = rsp.status();
+    let status = rsp.response.status();
+    if status.is_redirection() {
+        let location = rsp
+            .response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .map(String::from);
+        return Err(Error::UnexpectedRedirect { location });
+    }
+
     let bytes = rsp.bytes().await?;
 
     if status.is_success() {
-        Ok(serde_json::from_slice(&bytes)?)
+        Ok(bytes)
     } else {
         let bitvavo_err: BitvavoError = serde_json::from_slice(&bytes)?;
-        Err(Error::Bitvavo {
-            code: bitvavo_err.error_code,
-            message: bitvavo_err.error,
-        })
+        Err(bitvavo_error(bitvavo_err.error_code, bitvavo_err.error))
+    }
+}
+
+/// A response whose body is read up to a maximum size.
+#[cfg(feature = "client")]
+struct BoundedResponse {
+    response: Response,
+    /// The slug of the request, to refer to in errors.
+    endpoint: String,
+    max_size: usize,
+}
+
+#[cfg(feature = "client")]
+impl BoundedResponse {
+    /// Read the body chunk by chunk, failing as soon as it grows past the maximum size.
+    async fn bytes(mut self) -> Result<Vec<u8>, Error> {
+        let endpoint = self.endpoint;
+        let too_large = |endpoint| Error::ResponseTooLarge {
+            endpoint,
+            limit: self.max_size,
+        };
+
+        let content_length = self.response.content_length().unwrap_or(0);
+        if content_length > self.max_size as u64 {
+            return Err(too_large(endpoint));
+        }
+
+        let mut bytes = Vec::with_capacity(content_length as usize);
+        loop {
+            let chunk = match self.response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(source) => return Err(Error::Reqwest { endpoint, source }),
+            };
+
+            if bytes.len() + chunk.len() > self.max_size {
+                return Err(too_large(endpoint));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Turn an error code and message returned by the API into an error, using a dedicated variant
+/// for the codes that have one.
+#[cfg(feature = "client")]
+fn bitvavo_error(code: u64, message: String) -> Error {
+    match code {
+        308 | 309 => Error::SignatureRejected { code, message },
+        302 | 304 => Error::TimestampOutOfRange { code, message },
+        _ => Error::Bitvavo { code, message },
+    }
+}
+
+/// Turn a page of items, fetched newest first with `tradeIdTo` set to `cursor`, into a [`Page`]
+/// whose cursor is the id of the oldest item.
+///
+/// A full page means there may be more, while a shorter one is the last. The item at the cursor
+/// itself is dropped, should the endpoint include it.
+#[cfg(feature = "client")]
+fn page<T>(
+    mut items: Vec<T>,
+    limit: u64,
+    cursor: Option<String>,
+    id: impl Fn(&T) -> &str,
+) -> Page<T> {
+    let full = items.len() as u64 >= limit;
+    let next_cursor = items
+        .last()
+        .filter(|_| full)
+        .map(|item| id(item).to_string());
+
+    if let Some(cursor) = cursor {
+        items.retain(|item| id(item) != cursor);
+    }
+
+    Page { items, next_cursor }
+}
+
+/// The HTTP client used by default, which does not follow redirects, so the signed headers of a
+/// request are never sent to another host.
+#[cfg(feature = "client")]
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("The HTTP client should build")
+}
+
+/// The maximum number of requests made concurrently by methods that make several.
+#[cfg(feature = "client")]
+const CONCURRENT_REQUESTS: usize = 4;
+
+/// The maximum number of items the history endpoints return in a single page.
+#[cfg(feature = "client")]
+const HISTORY_PAGE_LIMIT: u64 = 1000;
+
+/// An endpoint that takes a `limit` on the number of items it returns.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, Copy)]
+enum LimitedEndpoint {
+    Trades,
+    Candles,
+    MyTrades,
+    DepositHistory,
+    WithdrawalHistory,
+}
+
+#[cfg(feature = "client")]
+impl LimitedEndpoint {
+    /// The largest `limit` the endpoint accepts.
+    fn max(self) -> u64 {
+        match self {
+            LimitedEndpoint::Trades => 1000,
+            LimitedEndpoint::Candles => 1440,
+            LimitedEndpoint::MyTrades => 1000,
+            LimitedEndpoint::DepositHistory => HISTORY_PAGE_LIMIT,
+            LimitedEndpoint::WithdrawalHistory => HISTORY_PAGE_LIMIT,
+        }
+    }
+
+    /// Clamp `limit` to the largest the endpoint accepts, logging a warning if it is lowered.
+    fn clamp(self, limit: u64) -> u64 {
+        let max = self.max();
+        if limit > max {
+            tracing::warn!(endpoint = ?self, limit, max, "limit lowered to the endpoint's maximum");
+        }
+        limit.min(max)
+    }
+}
+
+/// Fetch all items of a history endpoint between `start` and `end` by walking back in time, one
+/// page at a time.
+///
+/// Pages are expected newest first, and are requested with `end` set to the timestamp of the
+/// oldest item seen so far. Items at that exact timestamp that are returned again are skipped,
+/// so the result is correct whether the endpoint treats `end` as inclusive or exclusive. If more
+/// items than fit in a page share a single timestamp, those that do not fit cannot be reached.
+#[cfg(feature = "client")]
+async fn paginate<T, F, Fut>(
+    start: Option<u64>,
+    end: Option<u64>,
+    limit: u64,
+    timestamp: impl Fn(&T) -> u64,
+    mut fetch: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(Option<u64>, Option<u64>, u64) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<T>>>,
+{
+    let mut items: Vec<T> = Vec::new();
+    let mut end = end;
+
+    loop {
+        let page = fetch(start, end, limit).await?;
+        let full = page.len() as u64 >= limit;
+
+        // The number of items already seen at the boundary, which may be returned again.
+        let seen = match end {
+            Some(end) if !items.is_empty() => items
+                .iter()
+                .rev()
+                .take_while(|item| timestamp(item) == end)
+                .count(),
+            _ => 0,
+        };
+
+        let before = items.len();
+        let mut skipped = 0;
+        for item in page {
+            if skipped < seen && Some(timestamp(&item)) == end {
+                skipped += 1;
+                continue;
+            }
+            items.push(item);
+        }
+
+        let oldest = match items.last() {
+            Some(item) => timestamp(item),
+            None => break,
+        };
+
+        if !full || items.len() == before {
+            break;
+        }
+
+        end = if Some(oldest) == end {
+            // A full page at a single timestamp; move past it to guarantee progress.
+            match oldest.checked_sub(1) {
+                Some(end) => Some(end),
+                None => break,
+            }
+        } else {
+            Some(oldest)
+        };
+
+        if matches!((start, end), (Some(start), Some(end)) if end < start) {
+            break;
+        }
     }
+
+    Ok(items)
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Error::Reqwest(err) => write!(f, "reqwest: {err}"),
+            #[cfg(feature = "client")]
+            Error::Reqwest { endpoint, source } => {
+                write!(f, "reqwest error on {endpoint}: {source}")
+            }
             Error::Serde(err) => write!(f, "serde: {err}"),
             Error::Bitvavo { code, message } => {
                 write!(f, "bitvavo: {code}: {message}")
             }
+            Error::SignatureRejected { code, message } => {
+                write!(f, "signature rejected: {code}: {message}")
+            }
+            Error::TimestampOutOfRange { code, message } => {
+                write!(f, "timestamp out of range: {code}: {message}")
+            }
+            #[cfg(feature = "client")]
             Error::InvalidSecret(err) => match err {
                 BadSecret::InvalidLength(err) => write!(f, "invalid secret: {err}"),
                 BadSecret::Hex(err) => write!(f, "invalid secret: {err}"),
+                BadSecret::EmptyKey => write!(f, "invalid secret: empty API key"),
+                BadSecret::EmptySecret => write!(f, "invalid secret: empty API secret"),
+            },
+            Error::InvalidOrder(err) => write!(f, "invalid order: {err}"),
+            Error::InvalidNumber { field, value } => {
+                write!(f, "invalid number in {field}: {value:?}")
+            }
+            Error::NonceMismatch { expected, received } => {
+                write!(
+                    f,
+                    "nonce mismatch: expected {expected}, received {received}"
+                )
+            }
+            Error::ResponseTooLarge { endpoint, limit } => {
+                write!(f, "response from {endpoint} larger than {limit} bytes")
+            }
+            Error::Unsupported(reason) => write!(f, "unsupported: {reason}"),
+            Error::UnexpectedRedirect { location } => match location {
+                Some(location) => write!(f, "unexpected redirect to {location}"),
+                None => write!(f, "unexpected redirect"),
             },
+            Error::UnknownMarkets(markets) => write!(f, "unknown markets: {}", markets.join(", ")),
+            #[cfg(feature = "chrono-tz")]
+            Error::DateOutOfRange(date) => write!(f, "date out of range: {date}"),
+            Error::Multiple(errs) => {
+                write!(f, "multiple errors: ")?;
+                for (i, err) in errs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{err}")?;
+                }
+                Ok(())
+            }
+            #[cfg(feature = "client")]
+            Error::Shared(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            #[cfg(feature = "client")]
+            Error::Reqwest { source, .. } => Some(source),
+            Error::Serde(err) => Some(err),
+            #[cfg(feature = "client")]
+            Error::Shared(err) => err.source(),
+            _ => None,
         }
     }
 }
 
-impl StdError for Error {}
+/// The broad kind of an [`Error`], for matching on without depending on its exact variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A request could not be sent, or no response was received.
+    Network,
+    /// A response, or a part of it, could not be decoded.
+    Decode,
+    /// The API rejected a request.
+    Api,
+    /// The API rejected the credentials or signature of a request, or their permissions.
+    Auth,
+    /// The API rejected a request for exceeding the rate limit.
+    RateLimited,
+    /// The API did not find the endpoint or the order requested.
+    NotFound,
+    /// The client was given invalid input, such as a bad secret or an invalid order.
+    Config,
+}
+
+impl Error {
+    /// Returns true if the error was caused by a request timing out.
+    #[cfg(feature = "client")]
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Error::Reqwest { source, .. } => source.is_timeout(),
+            Error::Shared(err) => err.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// The kind of the error.
+    ///
+    /// | Error                                     | Kind                    |
+    /// |-------------------------------------------|-------------------------|
+    /// | `Reqwest`                                 | `Network`               |
+    /// | `Serde`, `InvalidNumber`, `NonceMismatch` | `Decode`                |
+    /// | `ResponseTooLarge`                        | `Decode`                |
+    /// | `Bitvavo` with code `105`                 | `RateLimited`           |
+    /// | `Bitvavo` with code `110` or `240`        | `NotFound`              |
+    /// | `UnknownMarkets`                          | `NotFound`              |
+    /// | `Bitvavo` with a code from `300` to `399` | `Auth`                  |
+    /// | `SignatureRejected`, `TimestampOutOfRange`| `Auth`                  |
+    /// | Any other `Bitvavo`, `UnexpectedRedirect` | `Api`                   |
+    /// | `InvalidSecret`, `InvalidOrder`           | `Config`                |
+    /// | `Unsupported`, `DateOutOfRange`           | `Config`                |
+    /// | `Multiple`                                | that of the first error |
+    /// | `Shared`                                  | that of the shared error|
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            #[cfg(feature = "client")]
+            Error::Reqwest { .. } => ErrorKind::Network,
+            Error::Serde(_)
+            | Error::InvalidNumber { .. }
+            | Error::NonceMismatch { .. }
+            | Error::ResponseTooLarge { .. } => ErrorKind::Decode,
+            Error::Bitvavo { code, .. } => match code {
+                105 => ErrorKind::RateLimited,
+                110 | 240 => ErrorKind::NotFound,
+                300..=399 => ErrorKind::Auth,
+                _ => ErrorKind::Api,
+            },
+            Error::UnexpectedRedirect { .. } => ErrorKind::Api,
+            Error::UnknownMarkets(_) => ErrorKind::NotFound,
+            Error::SignatureRejected { .. } | Error::TimestampOutOfRange { .. } => ErrorKind::Auth,
+            #[cfg(feature = "client")]
+            Error::InvalidSecret(_) => ErrorKind::Config,
+            Error::InvalidOrder(_) | Error::Unsupported(_) => ErrorKind::Config,
+            #[cfg(feature = "chrono-tz")]
+            Error::DateOutOfRange(_) => ErrorKind::Config,
+            Error::Multiple(errs) => errs.first().map_or(ErrorKind::Api, Error::kind),
+            #[cfg(feature = "client")]
+            Error::Shared(err) => err.kind(),
+        }
+    }
+}
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+#[cfg(feature = "client")]
 impl Default for Client {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "client")]
+#[derive(Clone)]
 struct Credentials {
     key: Zeroizing<String>,
-    secret: Zeroizing<String>,
+    signer: Arc<dyn Signer + Send + Sync>,
+}
+
+/// The key and signer are redacted, so credentials never end up in logs.
+#[cfg(feature = "client")]
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("key", &"***")
+            .field("secret", &"***")
+            .finish()
+    }
 }
 
 /// A client for the Bitvavo API.
+///
+/// Cloning a client is cheap. Clones share the connection pool, transport and signer of the
+/// original, so they can be handed to several tasks instead of wrapping the client in an `Arc`.
+#[cfg(feature = "client")]
+#[derive(Clone)]
 pub struct Client {
     client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    time_source: Arc<dyn TimeSource>,
     credentials: Option<Credentials>,
+    base_url: Url,
+    api_version: String,
+    retry: RetryConfig,
+    max_response_size: usize,
+    rate_limit_remaining: Arc<AtomicU64>,
+    rate_limit_warning: u64,
+    assets_flight: Arc<SingleFlight<Vec<Asset>>>,
+    markets_flight: Arc<SingleFlight<Vec<Market>>>,
+    asset_decimals: Arc<RwLock<HashMap<Symbol, u64>>>,
 }
 
-enum Method<T = ()> {
-    Get,
-    Post(T),
+#[cfg(feature = "client")]
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("credentials", &self.credentials)
+            .field("base_url", &self.base_url.as_str())
+            .field("api_version", &self.api_version)
+            .field("retry", &self.retry)
+            .finish_non_exhaustive()
+    }
 }
 
-impl Client {
-    /// Create a new client for the Bitvavo API.
-    pub fn new() -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            credentials: None,
-        }
-    }
+/// How requests are retried after transient failures.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Whether to retry GET requests that failed to connect or lost their connection, e.g. due
+    /// to a DNS failure or a connection reset. Defaults to `true`.
+    ///
+    /// Other requests, such as placing an order, are never retried, since they are not
+    /// idempotent.
+    pub retry_connect_errors: bool,
+    /// The maximum number of times a single request is retried. Defaults to `2`.
+    pub max_retries: u32,
+}
 
-    /// Create a new client for the Bitvavo API with credentials.
-    pub fn with_credentials(key: String, secret: String) -> Self {
+#[cfg(feature = "client")]
+impl Default for RetryConfig {
+    fn default() -> Self {
         Self {
-            client: reqwest::Client::new(),
-            credentials: Some(Credentials {
-                key: Zeroizing::new(key),
-                secret: Zeroizing::new(secret),
-            }),
+            retry_connect_errors: true,
+            max_retries: 2,
         }
     }
+}
 
-    fn request<T: Serialize>(
-        &self,
-        endpoint: impl AsRef<str>,
-        method: Method<T>,
-    ) -> Result<reqwest::RequestBuilder> {
-        let endpoint = endpoint.as_ref();
-        let slug = format!("/v2/{endpoint}");
+#[cfg(feature = "client")]
+const BASE_URL: &str = "https://api.bitvavo.com";
+#[cfg(feature = "client")]
+const API_VERSION: &str = "v2";
 
-        let url = format!("https://api.bitvavo.com{slug}");
+/// The default maximum size of a response body.
+#[cfg(feature = "client")]
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 8 * 1024 * 1024;
 
-        let (mut req, method, body) = match method {
-            Method::Get => {
-                let req = self.client.get(url);
-                (req, "GET", String::new())
-            }
-            Method::Post(body) => {
-                let req = self.client.post(url);
-                (req, "POST", serde_json::to_string(&body)?)
-            }
-        };
+/// The default remaining rate limit below which a warning is logged.
+#[cfg(feature = "client")]
+const DEFAULT_RATE_LIMIT_WARNING: u64 = 50;
 
-        if let Some(credentials) = &self.credentials {
-            let key = &*credentials.key;
-            let secret = &*credentials.secret;
+/// The header carrying the weight left in the current rate limit window.
+#[cfg(feature = "client")]
+const RATE_LIMIT_REMAINING: &str = "bitvavo-ratelimit-remaining";
 
-            let timestamp = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_millis()
-                .to_string();
+#[cfg(feature = "client")]
+enum Method<T = ()> {
+    Get,
+    Post(T),
+    Delete,
+}
 
-            type Hmac = hmac::Hmac<sha2::Sha256>;
-            let mut hmac = Hmac::new_from_slice(secret.as_bytes())?;
+/// A request to an endpoint, remembering the slug so errors can refer to it.
+#[cfg(feature = "client")]
+struct Request {
+    builder: reqwest::RequestBuilder,
+    transport: Arc<dyn Transport>,
+    slug: String,
+    retries: u32,
+    max_response_size: usize,
+    rate_limit_remaining: Arc<AtomicU64>,
+    rate_limit_warning: u64,
+}
 
-            hmac = hmac
-                .chain_update(&timestamp)
-                .chain_update(method)
-                .chain_update(slug)
-                .chain_update(&body);
+#[cfg(feature = "client")]
+impl Request {
+    fn timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.timeout(timeout);
+        self
+    }
 
-            let signature = hex::encode(hmac.finalize().into_bytes());
+    async fn send(self) -> Result<BoundedResponse> {
+        let slug = self.slug;
+        let wrap = |source| Error::Reqwest {
+            endpoint: slug.clone(),
+            source,
+        };
 
-            req = req.header("Bitvavo-Access-Key", key);
-            req = req.header("Bitvavo-Access-Timestamp", timestamp);
-            req = req.header("Bitvavo-Access-Signature", signature);
+        let mut request = self.builder.build().map_err(wrap)?;
+        let mut retries = self.retries;
+
+        loop {
+            let retry = match retries {
+                0 => None,
+                _ => request.try_clone(),
+            };
+
+            match (self.transport.execute(request).await, retry) {
+                (Err(err), Some(retry)) if is_connection_error(&err) => {
+                    request = retry;
+                    retries -= 1;
+                }
+                (result, _) => {
+                    let response = result.map_err(wrap)?;
+                    observe_rate_limit(
+                        &self.rate_limit_remaining,
+                        self.rate_limit_warning,
+                        &slug,
+                        &response,
+                    );
+                    return Ok(BoundedResponse {
+                        response,
+                        endpoint: slug,
+                        max_size: self.max_response_size,
+                    });
+                }
+            }
         }
-
-        req = req.body(body);
-
-        Ok(req)
     }
+}
 
-    #[inline(always)]
-    fn get(&self, endpoint: impl AsRef<str>) -> Result<reqwest::RequestBuilder> {
-        self.request(endpoint, Method::<()>::Get)
+/// Record the remaining rate limit reported by a response, warning if it is below `threshold`.
+#[cfg(feature = "client")]
+fn observe_rate_limit(latest: &AtomicU64, threshold: u64, slug: &str, response: &Response) {
+    let remaining = response
+        .headers()
+        .get(RATE_LIMIT_REMAINING)
+        .and_then(|value| value.to_str().ok()?.parse::<u64>().ok());
+
+    if let Some(remaining) = remaining {
+        latest.store(remaining, Ordering::Relaxed);
+
+        if remaining < threshold {
+            tracing::warn!(
+                endpoint = slug,
+                remaining,
+                threshold,
+                "Bitvavo rate limit approaching"
+            );
+        }
     }
+}
 
-    #[inline(always)]
-    fn post<T: Serialize>(
-        &self,
-        endpoint: impl AsRef<str>,
-        body: T,
-    ) -> Result<reqwest::RequestBuilder> {
-        self.request(endpoint, Method::Post(body))
+/// Whether an error means the request did not get a response due to a connection problem.
+#[cfg(feature = "client")]
+fn is_connection_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || (err.is_request() && !err.is_timeout())
+}
+
+#[cfg(feature = "client")]
+impl Client {
+    /// Create a new client for the Bitvavo API.
+    pub fn new() -> Self {
+        let client = http_client();
+        Self {
+            transport: Arc::new(client.clone()),
+            time_source: Arc::new(SystemClock),
+            client,
+            credentials: None,
+            base_url: Url::parse(BASE_URL).expect("The base URL should be valid"),
+            api_version: API_VERSION.to_string(),
+            retry: RetryConfig::default(),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            rate_limit_remaining: Arc::new(AtomicU64::new(u64::MAX)),
+            rate_limit_warning: DEFAULT_RATE_LIMIT_WARNING,
+            assets_flight: Arc::default(),
+            markets_flight: Arc::default(),
+            asset_decimals: Arc::default(),
+        }
     }
 
-    // Synchronization endpoints
+    /// Create a new client for the Bitvavo API with credentials.
+    ///
+    /// The secret is used as is to key the signature, as the hex string Bitvavo hands out, and
+    /// not hex decoded. Credentials are not checked here, so bad ones only surface as errors from
+    /// the API. Use [`try_with_credentials`](Client::try_with_credentials) to catch some of them
+    /// upfront.
+    pub fn with_credentials(key: String, secret: String) -> Self {
+        Self::with_signer(key, HmacSha256Signer::new(secret))
+    }
 
-    /// Get the current time.
+    /// Like [`with_credentials`](Client::with_credentials), but checking that neither the key
+    /// nor the secret are empty, and that the secret is a hex string like those Bitvavo hands
+    /// out.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
+    /// ```
     /// use bitvavo_api as bitvavo;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let t = c.time().await.unwrap();
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("not a secret");
     ///
-    /// println!("{t}");
-    /// # })
+    /// assert!(bitvavo::Client::try_with_credentials(key, secret).is_err());
     /// ```
-    pub async fn time(&self) -> Result<u64> {
-        #[derive(Deserialize, Serialize)]
-        struct Response {
-            time: u64,
+    pub fn try_with_credentials(key: String, secret: String) -> Result<Self> {
+        if key.is_empty() {
+            return Err(Error::InvalidSecret(BadSecret::EmptyKey));
         }
+        if secret.is_empty() {
+            return Err(Error::InvalidSecret(BadSecret::EmptySecret));
+        }
+        hex::decode(&secret)?;
 
-        let request = self.get("time")?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request::<Response>(http_response).await?;
+        Ok(Self::with_credentials(key, secret))
+    }
 
-        Ok(response.time)
+    /// Create a new client for the Bitvavo API with an API key, and a signer to sign requests
+    /// with.
+    pub fn with_signer(key: String, signer: impl Signer + Send + Sync + 'static) -> Self {
+        let client = http_client();
+        Self {
+            transport: Arc::new(client.clone()),
+            time_source: Arc::new(SystemClock),
+            client,
+            credentials: Some(Credentials {
+                key: Zeroizing::new(key),
+                signer: Arc::new(signer),
+            }),
+            base_url: Url::parse(BASE_URL).expect("The base URL should be valid"),
+            api_version: API_VERSION.to_string(),
+            retry: RetryConfig::default(),
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            rate_limit_remaining: Arc::new(AtomicU64::new(u64::MAX)),
+            rate_limit_warning: DEFAULT_RATE_LIMIT_WARNING,
+            assets_flight: Arc::default(),
+            markets_flight: Arc::default(),
+            asset_decimals: Arc::default(),
+        }
     }
 
-    /// Get all the assets.
+    /// Use the given transport to send requests, instead of sending them over HTTP directly.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
-    /// use bitvavo_api as bitvavo;
+    /// The default transport does not follow redirects, and reports them as
+    /// [`Error::UnexpectedRedirect`]. A [`reqwest::Client`] given here follows them, along with
+    /// the signed headers, unless it is built with [`reqwest::redirect::Policy::none`].
     ///
-    /// let c = bitvavo::Client::new();
-    /// let assets = c.assets().await.unwrap();
+    /// ```
+    /// use bitvavo_api as bitvavo;
     ///
-    /// println!("Number of assets: {}", assets.len());
-    /// # })
-    pub async fn assets(&self) -> Result<Vec<Asset>> {
-        let request = self.get("assets")?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+    /// let c = bitvavo::Client::new().with_transport(reqwest::Client::new());
+    /// ```
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
     }
 
-    /// Get the info of a particular asset.
+    /// Send requests to the given base URL, instead of `https://api.bitvavo.com`.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
+    /// This is useful for going through a proxy, or for testing against a local server. Note
+    /// that Bitvavo does not offer a public sandbox environment, so there is no preset for one.
+    ///
+    /// ```
     /// use bitvavo_api as bitvavo;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let asset = c.asset("BTC").await.unwrap();
+    /// let url = "http://localhost:8080".parse().unwrap();
+    /// let c = bitvavo::Client::new().with_base_url(url).unwrap();
+    /// ```
     ///
-    /// println!("Number of decimals used for BTC: {}", asset.decimals);
-    /// # })
-    pub async fn asset(&self, symbol: &str) -> Result<Asset> {
-        let request = self.get(format!("assets?symbol={symbol}"))?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+    /// Returns [`Error::Unsupported`] if the URL cannot have a path, such as a `mailto:` or
+    /// `data:` URL.
+    pub fn with_base_url(mut self, base_url: Url) -> Result<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(Error::Unsupported(
+                "the base URL must be able to have a path",
+            ));
+        }
 
-        Ok(response)
+        self.base_url = base_url;
+        Ok(self)
     }
 
-    /// Get all the markets.
+    /// Use the given source of the current time, instead of the system clock. This is the time
+    /// returned by [`now_millis`](Client::now_millis), used to timestamp signed requests among
+    /// others.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
+    /// ```
     /// use bitvavo_api as bitvavo;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let markets = c.markets().await.unwrap();
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
     ///
-    /// println!("Number of markets: {}", markets.len());
-    /// # })
-    pub async fn markets(&self) -> Result<Vec<Market>> {
-        let request = self.get("markets")?;
+    /// let c = bitvavo::Client::with_credentials(key, secret).with_time_source(|| 1548172481125);
+    /// ```
+    pub fn with_time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = Arc::new(time_source);
+        self
+    }
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+    /// The current time in milliseconds since the Unix epoch, according to the client's
+    /// [time source](Client::with_time_source).
+    ///
+    /// All time-derived behaviour of the client goes through this: the timestamps of signed
+    /// requests, the receipt times of [`Timed`] responses, and the cutoff of
+    /// [`cancel_stale_orders`](Client::cancel_stale_orders).
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new().with_time_source(|| 1548172481125);
+    /// assert_eq!(c.now_millis(), 1548172481125);
+    /// ```
+    pub fn now_millis(&self) -> u64 {
+        self.time_source.now_millis()
+    }
 
-        Ok(response)
+    /// Stamp a value with the current time.
+    fn timed<T>(&self, value: T, server_time: Option<u64>) -> Timed<T> {
+        Timed {
+            value,
+            received_at: UNIX_EPOCH + Duration::from_millis(self.now_millis()),
+            server_time,
+        }
     }
 
-    /// Get market information for a specific market.
+    /// Use the given configuration for retrying requests after transient failures.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
+    /// ```
     /// use bitvavo_api as bitvavo;
+    /// use bitvavo::RetryConfig;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let market = c.market("BTC-EUR").await.unwrap();
-    ///
-    /// println!("Price precision of BTC-EUR: {}", market.price_precision);
-    /// # })
-    pub async fn market(&self, pair: &str) -> Result<Market> {
-        let request = self.get(format!("markets?market={pair}"))?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+    /// let c = bitvavo::Client::new().with_retry_config(RetryConfig {
+    ///     retry_connect_errors: false,
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
     }
 
-    // Market data endpoints
+    /// Use the given API version for all requests, instead of the default `v2`.
+    ///
+    /// The version is part of the path of each request, and is therefore also signed.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new().with_api_version("v3");
+    /// ```
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = version.into();
+        self
+    }
 
-    /// Get the order book for a particular market.
+    /// Limit the size of response bodies to `bytes`, instead of the default 8 MiB.
     ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
+    /// Bodies are read as they arrive, and a response is abandoned with
+    /// [`Error::ResponseTooLarge`] as soon as it grows past the limit, so a misbehaving server or
+    /// proxy cannot exhaust memory.
+    ///
+    /// ```
     /// use bitvavo_api as bitvavo;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let ob = c.order_book("BTC-EUR", Some(2)).await.unwrap();
+    /// let c = bitvavo::Client::new().with_max_response_size(1024 * 1024);
+    /// ```
+    pub fn with_max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = bytes;
+        self
+    }
+
+    /// Log a warning through [`tracing`] whenever a response reports less than `threshold` left
+    /// of the rate limit, instead of the default of 50.
     ///
-    /// println!("Number of bids: {}", ob.bids.len());
-    /// # })
     /// ```
-    pub async fn order_book(&self, market: &str, depth: Option<u64>) -> Result<OrderBook> {
-        let mut url = format!("{market}/book");
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new().with_rate_limit_warning(100);
+    /// ```
+    pub fn with_rate_limit_warning(mut self, threshold: u64) -> Self {
+        self.rate_limit_warning = threshold;
+        self
+    }
 
-        if let Some(depth) = depth {
-            url.push_str(&format!("?depth={depth}"));
+    /// The weight left in the current rate limit window, as reported by the latest response, or
+    /// `None` if no response has reported it yet.
+    ///
+    /// The rate limit applies to the API key, or the IP address for public endpoints, so it is
+    /// shared between clones of a client.
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        match self.rate_limit_remaining.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            remaining => Some(remaining),
         }
-
-        let request = self.get(url)?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
     }
 
-    /// Get the trades for a particular market.
+    /// Create a client serving canned responses, for testing code using the client without
+    /// network access.
     ///
-    /// ```no_run
+    /// See [`MockTransport`] for the responses served and how to override them. The client has
+    /// placeholder credentials, so account endpoints can be called too.
+    ///
+    /// ```
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let trades = c.trades("BTC-EUR", None, None, None, None, None).await.unwrap();
+    /// let c = bitvavo::Client::mock();
+    /// let t = c.time().await.unwrap();
     ///
-    /// println!("Number of trades: {}", trades.len());
+    /// assert_eq!(t, 1539180275424);
     /// # })
     /// ```
-    pub async fn trades(
-        &self,
-        market: &str,
-        limit: Option<u64>,
-        start: Option<u64>,
-        end: Option<u64>,
-        trade_id_from: Option<String>,
-        trade_id_to: Option<String>,
-    ) -> Result<Vec<Trade>> {
-        let mut url = format!("{market}/trades");
+    ///
+    /// [`MockTransport`]: testing::MockTransport
+    #[cfg(feature = "testing")]
+    pub fn mock() -> Self {
+        Self::with_credentials("mock-key".to_string(), "mock-secret".to_string())
+            .with_transport(testing::MockTransport::with_fixtures())
+    }
 
-        if let Some(limit) = limit {
-            url.push_str(&format!("?limit={limit}"));
-        }
-        if let Some(start) = start {
-            url.push_str(&format!("&start={start}"));
+    /// Build the URL for an endpoint from its path segments and query parameters, encoding both.
+    fn url(&self, segments: &[&str], query: &[(&str, String)]) -> Url {
+        self.versioned_url(&self.api_version, segments, query)
+    }
+
+    /// Build the URL for an endpoint under a particular API version.
+    fn versioned_url(&self, version: &str, segments: &[&str], query: &[(&str, String)]) -> Url {
+        let mut url = self.base_url.clone();
+
+        url.path_segments_mut()
+            .expect("The base URL is checked to have a path by with_base_url")
+            .pop_if_empty()
+            .push(version)
+            .extend(segments);
+
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
         }
-        if let Some(end) = end {
-            url.push_str(&format!("&end={end}"));
+
+        url
+    }
+
+    /// The part of an endpoint URL that is signed: `/{version}/{endpoint}` and its query.
+    ///
+    /// The signature must be computed over exactly what is sent, so the slug is taken from the
+    /// already encoded URL. Any path the base URL has, such as a proxy prefix, is not part of it.
+    fn slug(&self, url: &Url) -> String {
+        let prefix = self.base_url.path().trim_end_matches('/');
+        let path = url.path();
+        let path = path.strip_prefix(prefix).unwrap_or(path);
+
+        match url.query() {
+            Some(query) => format!("{path}?{query}"),
+            None => path.to_string(),
         }
-        if let Some(trade_id_from) = trade_id_from {
-            url.push_str(&format!("&tradeIdFrom={trade_id_from}"));
+    }
+
+    /// Build a request, signing it if `signed` is true and the client has credentials.
+    fn request<T: Serialize>(&self, url: Url, method: Method<T>, signed: bool) -> Result<Request> {
+        let slug = self.slug(&url);
+
+        let retries = match method {
+            Method::Get if self.retry.retry_connect_errors => self.retry.max_retries,
+            _ => 0,
+        };
+
+        let (mut req, method, body) = match method {
+            Method::Get => {
+                let req = self.client.get(url);
+                (req, "GET", String::new())
+            }
+            Method::Post(body) => {
+                let req = self.client.post(url);
+                (req, "POST", serde_json::to_string(&body)?)
+            }
+            Method::Delete => {
+                let req = self.client.delete(url);
+                (req, "DELETE", String::new())
+            }
+        };
+
+        if signed && self.credentials.is_none() {
+            return Err(Error::Unsupported(
+                "account endpoints need credentials, see Client::with_credentials",
+            ));
         }
-        if let Some(trade_id_to) = trade_id_to {
-            url.push_str(&format!("&tradeIdTo={trade_id_to}"));
+
+        if let Some(credentials) = self.credentials.as_ref().filter(|_| signed) {
+            let key = &*credentials.key;
+
+            let timestamp = self.now_millis().to_string();
+
+            let signature = credentials.signer.sign(&timestamp, method, &slug, &body)?;
+
+            req = req.header("Bitvavo-Access-Key", key);
+            req = req.header("Bitvavo-Access-Timestamp", timestamp);
+            req = req.header("Bitvavo-Access-Signature", signature);
         }
 
-        let request = self.get(url)?;
+        req = req.body(body);
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+        Ok(Request {
+            builder: req,
+            transport: self.transport.clone(),
+            slug,
+            retries,
+            max_response_size: self.max_response_size,
+            rate_limit_remaining: self.rate_limit_remaining.clone(),
+            rate_limit_warning: self.rate_limit_warning,
+        })
+    }
 
-        Ok(response)
+    /// Build a GET request for a public endpoint. These are never signed, so the API key is
+    /// only sent when it is needed.
+    #[inline(always)]
+    fn get(&self, url: Url) -> Result<Request> {
+        self.request(url, Method::<()>::Get, false)
     }
 
-    /// Get candles for a particular market.
+    /// Build a signed GET request for an account endpoint.
+    #[inline(always)]
+    fn signed_get(&self, url: Url) -> Result<Request> {
+        self.request(url, Method::<()>::Get, true)
+    }
+
+    /// Build a signed POST request for an account endpoint.
+    #[inline(always)]
+    fn signed_post<T: Serialize>(&self, url: Url, body: T) -> Result<Request> {
+        self.request(url, Method::Post(body), true)
+    }
+
+    /// Build a signed DELETE request for an account endpoint.
+    #[inline(always)]
+    fn signed_delete(&self, url: Url) -> Result<Request> {
+        self.request(url, Method::<()>::Delete, true)
+    }
+
+    /// Perform a GET request on an arbitrary endpoint, returning both the typed response and the
+    /// raw JSON it was decoded from.
+    ///
+    /// The endpoint is relative to the API root, e.g. `ticker/price?market=BTC-EUR`. This is
+    /// meant for logging and auditing, and is slightly more costly than the typed methods. The
+    /// request is not signed, so it is only for public endpoints.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
-    /// use bitvavo::types::CandleInterval;
+    /// use bitvavo::types::TickerPrice;
     ///
     /// let c = bitvavo::Client::new();
-    /// let cs = c.candles("BTC-EUR", CandleInterval::OneDay, Some(1), None, None).await.unwrap();
+    /// let (ticker, raw) = c
+    ///     .get_with_raw::<TickerPrice>("ticker/price?market=BTC-EUR")
+    ///     .await
+    ///     .unwrap();
     ///
-    /// println!("High for BTC-EUR at {} was {}", cs[0].time, cs[0].high);
+    /// println!("Price for {}: {raw}", ticker.market);
     /// # })
     /// ```
-    pub async fn candles(
+    pub async fn get_with_raw<T: DeserializeOwned>(
         &self,
-        market: &str,
-        interval: CandleInterval,
-        limit: Option<u16>,
-        start: Option<u64>,
-        end: Option<u64>,
-    ) -> Result<Vec<OHLCV>> {
-        let mut url = format!("{market}/candles?interval={interval}");
-
-        if let Some(limit) = limit {
-            url.push_str(&format!("&limit={limit}"));
-        }
-        if let Some(start) = start {
-            url.push_str(&format!("&start={start}"));
-        }
-        if let Some(end) = end {
-            url.push_str(&format!("&end={end}"));
-        }
-
-        let request = self.get(url)?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+        endpoint: &str,
+    ) -> Result<(T, serde_json::Value)> {
+        self.get_versioned_with_raw(&self.api_version, endpoint)
+            .await
     }
 
-    /// Get all the tickers.
+    /// Like [`get_with_raw`](Client::get_with_raw), but under a particular API version instead of
+    /// the one the client is configured with.
+    ///
+    /// This allows endpoints only available in a newer version of the API to be used alongside
+    /// the ones of the current version.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
     /// let c = bitvavo::Client::new();
-    /// let ms = c.ticker_prices().await.unwrap();
+    /// let (time, _) = c
+    ///     .get_versioned_with_raw::<serde_json::Value>("v3", "time")
+    ///     .await
+    ///     .unwrap();
     ///
-    /// println!("Number of markets: {}", ms.len());
+    /// println!("{time}");
     /// # })
     /// ```
-    pub async fn ticker_prices(&self) -> Result<Vec<TickerPrice>> {
-        let request = self.get("ticker/price")?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+    pub async fn get_versioned_with_raw<T: DeserializeOwned>(
+        &self,
+        version: &str,
+        endpoint: &str,
+    ) -> Result<(T, serde_json::Value)> {
+        self.raw(version, endpoint, false).await
     }
 
-    /// Get the ticker for a particular market.
+    /// Like [`get_with_raw`](Client::get_with_raw), but signed, for account endpoints.
+    ///
+    /// Fails with [`Error::Unsupported`] if the client has no credentials.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Balance;
     ///
-    /// let c = bitvavo::Client::new();
-    /// let m = c.ticker_price("BTC-EUR").await.unwrap();
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
     ///
-    /// println!("Price for BTC-EUR: {}", m.price.unwrap_or_default());
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let (balances, raw) = c
+    ///     .signed_get_with_raw::<Vec<Balance>>("balance")
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("Number of balances: {}, as JSON: {raw}", balances.len());
     /// # })
     /// ```
-    pub async fn ticker_price(&self, pair: &str) -> Result<TickerPrice> {
-        let request = self.get(format!("ticker/price?market={pair}"))?;
+    pub async fn signed_get_with_raw<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+    ) -> Result<(T, serde_json::Value)> {
+        self.raw(&self.api_version, endpoint, true).await
+    }
+
+    async fn raw<T: DeserializeOwned>(
+        &self,
+        version: &str,
+        endpoint: &str,
+        signed: bool,
+    ) -> Result<(T, serde_json::Value)> {
+        let (path, query) = match endpoint.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (endpoint, None),
+        };
+
+        let segments: Vec<&str> = path.split('/').collect();
+        let mut url = self.versioned_url(version, &segments, &[]);
+        url.set_query(query);
+
+        let request = self.request(url, Method::<()>::Get, signed)?;
 
         let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+        let raw = response_from_request::<serde_json::Value>(http_response).await?;
+        let response = T::deserialize(&raw)?;
 
-        Ok(response)
+        Ok((response, raw))
     }
 
-    /// Retrieve the highest buy and lowest sell prices currently available for all markets.
+    // Synchronization endpoints
+
+    /// Get the current time.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
     /// let c = bitvavo::Client::new();
-    /// let tb = c.ticker_books().await.unwrap();
+    /// let t = c.time().await.unwrap();
     ///
-    /// println!("Number of tickers: {}", tb.len());
+    /// println!("{t}");
     /// # })
     /// ```
-    pub async fn ticker_books(&self) -> Result<Vec<TickerBook>> {
-        let request = self.get("ticker/book")?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+    pub async fn time(&self) -> Result<u64> {
+        self.time_inner(None).await
     }
 
-    /// Retrieve the highest buy and lowest sell prices currently available for a given market.
+    /// Get the current time, failing if the request takes longer than `timeout`.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
     /// use bitvavo_api as bitvavo;
     ///
     /// let c = bitvavo::Client::new();
-    /// let tb = c.ticker_book("BTC-EUR").await.unwrap();
+    /// let t = c.time_with_timeout(Duration::from_millis(500)).await.unwrap();
     ///
-    /// println!("Highest buy price for BTC-EUR: {}", tb.ask.unwrap());
+    /// println!("{t}");
     /// # })
     /// ```
-    pub async fn ticker_book(&self, market: &str) -> Result<TickerBook> {
-        let request = self.get(format!("ticker/book?market={market}"))?;
+    pub async fn time_with_timeout(&self, timeout: Duration) -> Result<u64> {
+        self.time_inner(Some(timeout)).await
+    }
+
+    async fn time_inner(&self, timeout: Option<Duration>) -> Result<u64> {
+        #[derive(Deserialize, Serialize)]
+        struct Response {
+            time: u64,
+        }
+
+        let mut request = self.get(self.url(&["time"], &[]))?;
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
 
         let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+        let response = response_from_request::<Response>(http_response).await?;
 
-        Ok(response)
+        Ok(response.time)
     }
 
-    /// Retrieve high, low, open, last, and volume information for trades for all markets over the previous 24h.
+    /// Measure the round-trip latency to the API, by timing a request for the current time.
+    ///
+    /// The response is checked for success, but not parsed.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
     /// let c = bitvavo::Client::new();
-    /// let t24h = c.tickers_24h().await.unwrap();
+    /// let latency = c.ping().await.unwrap();
     ///
-    /// println!("Number of tickers: {}", t24h.len());
+    /// println!("Latency: {latency:?}");
     /// # })
     /// ```
-    pub async fn tickers_24h(&self) -> Result<Vec<Ticker24h>> {
-        let request = self.get("ticker/24h")?;
+    pub async fn ping(&self) -> Result<Duration> {
+        let request = self.get(self.url(&["time"], &[]))?;
 
+        let start = Instant::now();
         let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+        response_unit(http_response).await?;
 
-        Ok(response)
+        Ok(start.elapsed())
     }
 
-    /// Retrieve high, low, open, last, and volume information for trades for a given market over the previous 24h.
+    /// Establish a connection to the API ahead of time, so the first real request doesn't pay for
+    /// the TCP and TLS handshakes.
+    ///
+    /// This makes a request for the current time, and should be called once at startup.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
     /// let c = bitvavo::Client::new();
-    /// let t24h = c.ticker_24h("BTC-EUR").await.unwrap();
-    ///
-    /// println!("24h ask for BTC-EUR: {}", t24h.ask.unwrap());
+    /// c.warmup().await.unwrap();
     /// # })
     /// ```
-    pub async fn ticker_24h(&self, market: &str) -> Result<Ticker24h> {
-        let request = self.get(format!("ticker/24h?market={market}"))?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
-
-        Ok(response)
+    pub async fn warmup(&self) -> Result<()> {
+        self.ping().await.map(drop)
     }
 
-    // Account endpoints
-
-    /// Retrieve information about the account.
+    /// Measure how far the server's clock is off from the local one, in milliseconds.
+    ///
+    /// The skew is the server's time minus the local time, so it is positive when the local
+    /// clock is behind the server's, and negative when it is ahead. The local time is read from
+    /// the client's time source, halfway through a request for the server's time, so the result
+    /// is off by at most half the round trip.
+    ///
+    /// This is purely diagnostic. Requests are always signed with the local time, so a large
+    /// skew shows up as [`Error::TimestampOutOfRange`].
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
-    ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let account = c.account().await.unwrap();
+    /// let c = bitvavo::Client::new();
+    /// let skew = c.server_time_skew().await.unwrap();
     ///
-    /// println!("Fee for maker orders: {}", account.fees.maker);
+    /// println!("The server is {skew}ms ahead");
     /// # })
-    pub async fn account(&self) -> Result<Account> {
-        let request = self.get("account")?;
-
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+    /// ```
+    pub async fn server_time_skew(&self) -> Result<i64> {
+        let before = self.now_millis();
+        let server = self.time().await?;
+        let after = self.now_millis();
 
-        Ok(response)
+        let local = before + after.saturating_sub(before) / 2;
+        Ok(server as i64 - local as i64)
     }
 
-    /// Retrieve balances for all assets in the account.
+    /// Get all the assets, in the order returned by the API.
+    ///
+    /// Concurrent calls on a client and its clones share a single request while it is in flight.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
-    ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let balances = c.balances().await.unwrap();
+    /// let c = bitvavo::Client::new();
+    /// let assets = c.assets().await.unwrap();
     ///
-    /// println!("Number of assets held: {}", balances.len());
+    /// println!("Number of assets: {}", assets.len());
     /// # })
-    pub async fn balances(&self) -> Result<Vec<Balance>> {
-        let request = self.get("balance")?;
+    pub async fn assets(&self) -> Result<Vec<Asset>> {
+        self.assets_flight
+            .run(|| async {
+                let request = self.get(self.url(&["assets"], &[]))?;
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+                let http_response = request.send().await?;
+                let response = response_from_request(http_response).await?;
 
-        Ok(response)
+                Ok(response)
+            })
+            .await
     }
 
-    /// Retrieve balances for a particular asset in the account.
+    /// Get all the assets, sorted by symbol.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
-    ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let balance = c.balance("BTC").await.unwrap();
+    /// let c = bitvavo::Client::new();
+    /// let assets = c.assets_sorted().await.unwrap();
     ///
-    /// println!("BTC available: {}", balance.available);
+    /// println!("First asset: {}", assets[0].symbol);
     /// # })
-    pub async fn balance(&self, symbol: &str) -> Result<Balance> {
-        let request = self.get(format!("balance?symbol={symbol}"))?;
-
+    /// ```
+    pub async fn assets_sorted(&self) -> Result<Vec<Asset>> {
+        let mut assets = self.assets().await?;
+        assets.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        Ok(assets)
+    }
+
+    /// Get the info of a particular asset.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let asset = c.asset("BTC").await.unwrap();
+    ///
+    /// println!("Number of decimals used for BTC: {}", asset.decimals);
+    /// # })
+    pub async fn asset(&self, symbol: impl Into<Symbol>) -> Result<Asset> {
+        let symbol = symbol.into();
+        let request = self.get(self.url(&["assets"], &[("symbol", symbol.to_string())]))?;
+
         let http_response = request.send().await?;
-        let response = response_from_request::<Vec<Balance>>(http_response).await?;
+        let response = response_from_request(http_response).await?;
 
-        Ok(response.into_iter().next().unwrap())
+        Ok(response)
     }
 
-    /// Get the fees that are charged for trading in a particular market. If no market is provided,
-    /// the fees for catrgory B are returned.
+    /// Get the number of decimals an asset is shown with, e.g. `8` for `BTC`.
+    ///
+    /// The decimals of all assets are fetched on the first call, and kept for the lifetime of the
+    /// client and its clones. They are only fetched again when asked for an asset that isn't
+    /// known yet, so a change to the decimals of a known asset is only picked up by a new client.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
+    /// let c = bitvavo::Client::new();
+    /// let decimals = c.asset_decimals("BTC").await.unwrap();
     ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let fees = c.fees(None).await.unwrap();
+    /// println!("Number of decimals used for BTC: {decimals}");
+    /// # })
+    /// ```
+    pub async fn asset_decimals(&self, symbol: impl Into<Symbol>) -> Result<u64> {
+        let symbol = symbol.into();
+
+        if let Some(decimals) = self.asset_decimals.read().unwrap().get(&symbol) {
+            return Ok(*decimals);
+        }
+
+        let assets = self.assets().await?;
+        {
+            let mut cache = self.asset_decimals.write().unwrap();
+            cache.extend(
+                assets
+                    .into_iter()
+                    .map(|asset| (Symbol::from(asset.symbol), asset.decimals)),
+            );
+            if let Some(decimals) = cache.get(&symbol) {
+                return Ok(*decimals);
+            }
+        }
+
+        // Not among all the assets, so ask for it alone to get the API's error for it.
+        let asset = self.asset(&symbol).await?;
+        self.asset_decimals
+            .write()
+            .unwrap()
+            .insert(symbol, asset.decimals);
+        Ok(asset.decimals)
+    }
+
+    /// Format an amount of an asset with the number of decimals the asset is shown with, as
+    /// given by [`asset_decimals`](Client::asset_decimals).
     ///
-    /// println!("Taker fee for category B: {}", fees.taker);
+    /// The amount is truncated, as with [`Rounding::default`], and padded with zeros.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::Decimal;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let amount: Decimal = "0.5".parse().unwrap();
+    ///
+    /// assert_eq!(c.format_amount("BTC", amount).await.unwrap(), "0.50000000");
     /// # })
-    pub async fn fees(&self, market: Option<&str>) -> Result<Fees> {
-        let url = match market {
-            Some(market) => format!("account/fees?market={market}"),
-            None => "account/fees".to_string(),
-        };
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub async fn format_amount(
+        &self,
+        symbol: impl Into<Symbol>,
+        amount: Decimal,
+    ) -> Result<String> {
+        self.format_amount_with(symbol, amount, Rounding::default())
+            .await
+    }
+
+    /// Like [`format_amount`](Client::format_amount), rounding with the amount mode of
+    /// `rounding`.
+    #[cfg(feature = "decimal")]
+    pub async fn format_amount_with(
+        &self,
+        symbol: impl Into<Symbol>,
+        amount: Decimal,
+        rounding: Rounding,
+    ) -> Result<String> {
+        let decimals = self.asset_decimals(symbol).await?;
+        Ok(format_amount(amount, decimals, rounding.amount))
+    }
+
+    /// Get all the markets, in the order returned by the API.
+    ///
+    /// Concurrent calls on a client and its clones share a single request while it is in flight.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.markets().await.unwrap();
+    ///
+    /// println!("Number of markets: {}", markets.len());
+    /// # })
+    pub async fn markets(&self) -> Result<Vec<Market>> {
+        self.markets_flight
+            .run(|| async {
+                let request = self.get(self.url(&["markets"], &[]))?;
+
+                let http_response = request.send().await?;
+                let response = response_from_request(http_response).await?;
+
+                Ok(response)
+            })
+            .await
+    }
+
+    /// Get all the markets, sorted by pair.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.markets_sorted().await.unwrap();
+    ///
+    /// println!("First market: {}", markets[0].pair);
+    /// # })
+    /// ```
+    pub async fn markets_sorted(&self) -> Result<Vec<Market>> {
+        let mut markets = self.markets().await?;
+        markets.sort_by(|a, b| a.pair.cmp(&b.pair));
+        Ok(markets)
+    }
+
+    /// Get all the markets, indexed by pair.
+    ///
+    /// This takes a single request, after which markets can be looked up without scanning the
+    /// list. The index holds every market in full, which is a few hundred entries on Bitvavo.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.market_index().await.unwrap();
+    ///
+    /// println!("Minimum order in BTC-EUR: {}", markets["BTC-EUR"].min_order_in_base_asset);
+    /// # })
+    /// ```
+    pub async fn market_index(&self) -> Result<HashMap<String, Market>> {
+        let markets = self.markets().await?;
+        Ok(markets.into_iter().map(|m| (m.pair.clone(), m)).collect())
+    }
+
+    /// Get the differences between a `previous` snapshot of the markets and the current markets,
+    /// such as newly listed markets or changed precisions.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.markets().await.unwrap();
+    ///
+    /// // Some time later.
+    /// let diff = c.diff_markets(&markets).await.unwrap();
+    /// for market in diff.added {
+    ///     println!("New market: {}", market.pair);
+    /// }
+    /// # })
+    /// ```
+    pub async fn diff_markets(&self, previous: &[Market]) -> Result<MarketDiff> {
+        let current = self.markets().await?;
+        Ok(MarketDiff::between(previous, &current))
+    }
 
-        let request = self.get(url)?;
+    /// Get market information for a specific market.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let market = c.market("BTC-EUR").await.unwrap();
+    ///
+    /// println!("Price precision of BTC-EUR: {}", market.price_precision);
+    /// # })
+    pub async fn market(&self, pair: impl Into<MarketPair>) -> Result<Market> {
+        let pair = pair.into();
+        let request = self.get(self.url(&["markets"], &[("market", pair.to_string())]))?;
 
         let http_response = request.send().await?;
         let response = response_from_request(http_response).await?;
@@ -654,25 +1670,161 @@ impl Client {
         Ok(response)
     }
 
-    // Transfer endpoints
+    /// Get all the markets that are currently trading.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.markets_tradable().await.unwrap();
+    ///
+    /// println!("Number of tradable markets: {}", markets.len());
+    /// # })
+    /// ```
+    pub async fn markets_tradable(&self) -> Result<Vec<Market>> {
+        self.markets_with_status(MarketStatus::Trading).await
+    }
 
-    /// Returns the deposit address or bank account information to increase the balance.
+    /// Get all the markets with the given status.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::MarketStatus;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
+    /// let c = bitvavo::Client::new();
+    /// let markets = c.markets_with_status(MarketStatus::Halted).await.unwrap();
     ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let deposit_info = c.deposit_info("BTC").await.unwrap();
+    /// println!("Number of halted markets: {}", markets.len());
+    /// # })
+    /// ```
+    pub async fn markets_with_status(&self, status: MarketStatus) -> Result<Vec<Market>> {
+        let mut markets = self.markets().await?;
+        markets.retain(|market| market.status == status);
+        Ok(markets)
+    }
+
+    /// Get the pairs of the markets that accept orders of the given type, such as the markets
+    /// supporting stop loss orders.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::OrderType;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let pairs = c.markets_supporting(OrderType::StopLoss).await.unwrap();
+    ///
+    /// println!("Markets with stop loss orders: {:?}", pairs);
+    /// # })
+    /// ```
+    pub async fn markets_supporting(&self, order_type: OrderType) -> Result<Vec<String>> {
+        let markets = self.markets().await?;
+        Ok(markets
+            .into_iter()
+            .filter(|market| market.order_types.iter().any(|t| t == order_type.as_str()))
+            .map(|market| market.pair)
+            .collect())
+    }
+
+    /// Get the distinct quote currencies of all the markets, e.g. `EUR`.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let quotes = c.quote_currencies().await.unwrap();
+    ///
+    /// println!("Quote currencies: {:?}", quotes);
+    /// # })
+    /// ```
+    pub async fn quote_currencies(&self) -> Result<BTreeSet<String>> {
+        let markets = self.markets().await?;
+        Ok(markets.into_iter().map(|market| market.quote).collect())
+    }
+
+    /// Get the distinct base currencies of all the markets, e.g. `BTC`.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let bases = c.base_currencies().await.unwrap();
+    ///
+    /// println!("Number of base currencies: {}", bases.len());
+    /// # })
+    /// ```
+    pub async fn base_currencies(&self) -> Result<BTreeSet<String>> {
+        let markets = self.markets().await?;
+        Ok(markets.into_iter().map(|market| market.base).collect())
+    }
+
+    // Market data endpoints
+
+    /// Get the order book for a particular market.
+    ///
+    /// The depth is either a [`Depth`], or an `Option<u64>` where `None` is the full book. Depths
+    /// beyond [`Depth::MAX`] are capped to it.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Depth;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let ob = c.order_book("BTC-EUR", Depth::Levels(2)).await.unwrap();
+    ///
+    /// println!("Number of bids: {}", ob.bids.len());
+    /// # })
+    /// ```
+    pub async fn order_book(
+        &self,
+        market: impl Into<MarketPair>,
+        depth: impl Into<Depth>,
+    ) -> Result<OrderBook> {
+        self.order_book_as(market.into(), depth.into()).await
+    }
+
+    /// Get the order book for a particular market, with the quotes parsed into decimals.
+    ///
+    /// This behaves like [`Client::order_book`], but parses each price and amount directly,
+    /// without allocating strings for them.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Depth;
     ///
-    /// println!("BTC deposit address: {}", deposit_info.address);
+    /// let c = bitvavo::Client::new();
+    /// let ob = c.order_book_numeric("BTC-EUR", Depth::Levels(2)).await.unwrap();
+    ///
+    /// println!("Best bid: {}", ob.bids[0].price);
     /// # })
     /// ```
-    pub async fn deposit_info(&self, symbol: &str) -> Result<DepositInfo> {
-        let request = self.get(format!("deposit?symbol={symbol}"))?;
+    #[cfg(feature = "decimal")]
+    pub async fn order_book_numeric(
+        &self,
+        market: impl Into<MarketPair>,
+        depth: impl Into<Depth>,
+    ) -> Result<NumericOrderBook> {
+        self.order_book_as(market.into(), depth.into()).await
+    }
+
+    async fn order_book_as<T: DeserializeOwned>(
+        &self,
+        market: MarketPair,
+        depth: Depth,
+    ) -> Result<T> {
+        let mut query = Vec::new();
+
+        if let Some(depth) = depth.levels() {
+            query.push(("depth", depth.to_string()));
+        }
+
+        let request = self.get(self.url(&[market.as_str(), "book"], &query))?;
 
         let http_response = request.send().await?;
         let response = response_from_request(http_response).await?;
@@ -680,44 +1832,50 @@ impl Client {
         Ok(response)
     }
 
-    /// Returns the deposit history of the account.
+    /// Get the trades for a particular market.
+    ///
+    /// At most 1000 trades are returned, and a larger `limit` is lowered to that.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
-    /// use bitvavo::types::Deposit;
-    ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
     ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let deposit_history = c.deposit_history(None, None, None, None).await.unwrap();
+    /// let c = bitvavo::Client::new();
+    /// let trades = c.trades("BTC-EUR", None, None, None, None, None).await.unwrap();
     ///
-    /// println!("Number of deposits: {}", deposit_history.len());
+    /// println!("Number of trades: {}", trades.len());
     /// # })
-    pub async fn deposit_history(
+    /// ```
+    pub async fn trades(
         &self,
-        symbol: Option<&str>,
+        market: impl Into<MarketPair>,
         limit: Option<u64>,
         start: Option<u64>,
         end: Option<u64>,
-    ) -> Result<Vec<Deposit>> {
-        let mut url = String::from("depositHistory");
+        trade_id_from: Option<String>,
+        trade_id_to: Option<String>,
+    ) -> Result<Vec<Trade>> {
+        let market = market.into();
+        let mut query = Vec::new();
 
-        if let Some(symbol) = symbol {
-            url.push_str(&format!("?symbol={symbol}"));
-        }
         if let Some(limit) = limit {
-            url.push_str(&format!("&limit={limit}"));
+            let limit = LimitedEndpoint::Trades.clamp(limit);
+            query.push(("limit", limit.to_string()));
         }
         if let Some(start) = start {
-            url.push_str(&format!("&start={start}"));
+            query.push(("start", start.to_string()));
         }
         if let Some(end) = end {
-            url.push_str(&format!("&end={end}"));
+            query.push(("end", end.to_string()));
+        }
+        if let Some(trade_id_from) = trade_id_from {
+            query.push(("tradeIdFrom", trade_id_from));
+        }
+        if let Some(trade_id_to) = trade_id_to {
+            query.push(("tradeIdTo", trade_id_to));
         }
 
-        let request = self.get(url)?;
+        let request = self.get(self.url(&[market.as_str(), "trades"], &query))?;
 
         let http_response = request.send().await?;
         let response = response_from_request(http_response).await?;
@@ -725,255 +1883,3540 @@ impl Client {
         Ok(response)
     }
 
-    /// Withdraw an asset from the account to a given address.
+    /// Get a page of the trades in a particular market, newest first.
+    ///
+    /// The first page is requested without a cursor, and each following one with the
+    /// `next_cursor` of the page before it. The cursor is the id of the oldest trade in a page,
+    /// and the next page holds the trades before it.
     ///
     /// ```no_run
     /// # tokio_test::block_on(async {
     /// use bitvavo_api as bitvavo;
-    /// use bitvavo::types::WithdrawOrder;
     ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
+    /// let c = bitvavo::Client::new();
     ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let response = c.withdraw(WithdrawOrder {
-    ///     symbol: String::from("BTC"),
-    ///     amount: String::from("0.1"),
-    ///     address: String::from("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
-    ///     payment_id: None,
-    ///     internal: false,
-    ///     add_withdrawal_fee: false,
-    /// }).await.unwrap();
+    /// let mut cursor = None;
+    /// for _ in 0..3 {
+    ///     let page = c.trades_paged("BTC-EUR", 100, cursor).await.unwrap();
+    ///     println!("Number of trades: {}", page.items.len());
     ///
-    /// println!("Withdrawal succeeded: {}", response.success);
+    ///     cursor = page.next_cursor;
+    ///     if cursor.is_none() {
+    ///         break;
+    ///     }
+    /// }
     /// # })
     /// ```
-    pub async fn withdraw(&self, order: WithdrawOrder) -> Result<WithdrawalOrderResponse> {
-        let request = self.post("withdrawal", order)?;
+    pub async fn trades_paged(
+        &self,
+        market: impl Into<MarketPair>,
+        limit: u64,
+        cursor: Option<String>,
+    ) -> Result<Page<Trade>> {
+        let limit = LimitedEndpoint::Trades.clamp(limit);
+        let trades = self
+            .trades(market, Some(limit), None, None, None, cursor.clone())
+            .await?;
+        Ok(page(trades, limit, cursor, |t| &t.id))
+    }
+
+    /// Get candles for a particular market.
+    ///
+    /// At most 1440 candles are returned, and a larger `limit` is lowered to that.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::CandleInterval;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let cs = c.candles("BTC-EUR", CandleInterval::OneDay, Some(1), None, None).await.unwrap();
+    ///
+    /// println!("High for BTC-EUR at {} was {}", cs[0].time, cs[0].high);
+    /// # })
+    /// ```
+    pub async fn candles(
+        &self,
+        market: impl Into<MarketPair>,
+        interval: CandleInterval,
+        limit: Option<u16>,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<Vec<OHLCV>> {
+        let market = market.into();
+        let mut query = vec![("interval", interval.as_str().to_string())];
+
+        if let Some(limit) = limit {
+            let limit = LimitedEndpoint::Candles.clamp(limit.into());
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(start) = start {
+            query.push(("start", start.to_string()));
+        }
+        if let Some(end) = end {
+            query.push(("end", end.to_string()));
+        }
+
+        // Candles are a bare array, but accept them wrapped in an object too, should the
+        // response ever gain metadata alongside them.
+        #[derive(Deserialize)]
+        struct CandlesResponse {
+            candles: Vec<OHLCV>,
+        }
+
+        let request = self.get(self.url(&[market.as_str(), "candles"], &query))?;
+
+        let http_response = request.send().await?;
+        let bytes = success_bytes(http_response).await?;
+        let bytes = bytes.as_ref();
+
+        match decode::<Vec<OHLCV>>(bytes) {
+            Ok(candles) => Ok(candles),
+            Err(err) => decode::<CandlesResponse>(bytes)
+                .map(|response| response.candles)
+                .map_err(|_| err),
+        }
+    }
+
+    /// Get candles for a particular market at several intervals at once, keyed by the string
+    /// form of each interval.
+    ///
+    /// The requests are made concurrently, a few at a time. If any of them fail, all the errors
+    /// are returned together as [`Error::Multiple`].
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::CandleInterval;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let intervals = [CandleInterval::OneMinute, CandleInterval::OneHour];
+    /// let cs = c.candles_multi("BTC-EUR", &intervals, Some(10)).await.unwrap();
+    ///
+    /// println!("Number of hourly candles: {}", cs["1h"].len());
+    /// # })
+    /// ```
+    pub async fn candles_multi(
+        &self,
+        market: impl Into<MarketPair>,
+        intervals: &[CandleInterval],
+        limit: Option<u16>,
+    ) -> Result<HashMap<String, Vec<OHLCV>>> {
+        let market: &MarketPair = &market.into();
+        let results: Vec<_> = stream::iter(intervals)
+            .map(|&interval| async move {
+                let candles = self.candles(market, interval, limit, None, None).await;
+                (interval.as_str().to_string(), candles)
+            })
+            .buffer_unordered(CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut candles = HashMap::with_capacity(results.len());
+        let mut errs = Vec::new();
+
+        for (interval, result) in results {
+            match result {
+                Ok(cs) => {
+                    candles.insert(interval, cs);
+                }
+                Err(err) => errs.push(err),
+            }
+        }
+
+        if !errs.is_empty() {
+            return Err(Error::Multiple(errs));
+        }
+
+        Ok(candles)
+    }
+
+    /// Get the candles making up a calendar day in the given timezone.
+    ///
+    /// The exchange aligns candles to UTC, so a daily candle covers midnight to midnight UTC
+    /// rather than the local day. Instead, this fetches candles of a shorter interval between
+    /// the local day's boundaries, as given by [`local_day_bounds`], which can then be
+    /// aggregated. The interval should divide the timezone's offset from UTC, which an hour
+    /// does for nearly every timezone; otherwise the first candle starts after local midnight.
+    ///
+    /// The candles are fetched in as many requests as the day needs, so a day that daylight
+    /// saving time makes 25 hours long has all of its candles. Returns [`Error::DateOutOfRange`]
+    /// for a day that has no bounds, such as one before 1970.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::CandleInterval;
+    /// use chrono::NaiveDate;
+    /// use chrono_tz::Europe::Amsterdam;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let date = NaiveDate::from_ymd_opt(2024, 10, 27).unwrap();
+    /// let cs = c
+    ///     .candles_for_local_day("BTC-EUR", CandleInterval::OneHour, Amsterdam, date)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("Number of hours in the day: {}", cs.len());
+    /// # })
+    /// ```
+    #[cfg(feature = "chrono-tz")]
+    pub async fn candles_for_local_day(
+        &self,
+        market: impl Into<MarketPair>,
+        interval: CandleInterval,
+        tz: chrono_tz::Tz,
+        date: chrono::NaiveDate,
+    ) -> Result<Vec<OHLCV>> {
+        let market = market.into();
+        let (start, end) = local_day_bounds(tz, date).ok_or(Error::DateOutOfRange(date))?;
+
+        let interval_millis = interval.duration().as_millis() as u64;
+        let max = LimitedEndpoint::Candles.max();
+        let limit = (end - start).div_ceil(interval_millis).min(max);
+
+        let mut candles = paginate(
+            Some(start),
+            Some(end),
+            limit,
+            |candle: &OHLCV| candle.time,
+            |start, end, limit| self.candles(&market, interval, Some(limit as u16), start, end),
+        )
+        .await?;
+        candles.retain(|c| c.time >= start && c.time < end);
+
+        Ok(candles)
+    }
+
+    /// Get all the tickers.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let ms = c.ticker_prices().await.unwrap();
+    ///
+    /// println!("Number of markets: {}", ms.len());
+    /// # })
+    /// ```
+    pub async fn ticker_prices(&self) -> Result<Vec<TickerPrice>> {
+        let request = self.get(self.url(&["ticker", "price"], &[]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Get the ticker for a particular market.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let m = c.ticker_price("BTC-EUR").await.unwrap();
+    ///
+    /// println!("Price for BTC-EUR: {}", m.price.unwrap_or_default());
+    /// # })
+    /// ```
+    pub async fn ticker_price(&self, pair: impl Into<MarketPair>) -> Result<TickerPrice> {
+        let pair = pair.into();
+        let request = self.get(self.url(&["ticker", "price"], &[("market", pair.to_string())]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Retrieve the highest buy and lowest sell prices currently available for all markets.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let tb = c.ticker_books().await.unwrap();
+    ///
+    /// println!("Number of tickers: {}", tb.len());
+    /// # })
+    /// ```
+    pub async fn ticker_books(&self) -> Result<Vec<TickerBook>> {
+        let request = self.get(self.url(&["ticker", "book"], &[]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Retrieve the highest buy and lowest sell prices currently available for the given markets,
+    /// in the order they are given.
+    ///
+    /// The ticker books of all markets are fetched in a single request, and filtered. Markets
+    /// without a ticker book are reported together as [`Error::UnknownMarkets`].
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let tbs = c.ticker_books_for(["BTC-EUR", "ETH-EUR"]).await.unwrap();
+    ///
+    /// println!("Highest buy price for ETH-EUR: {}", tbs[1].bid.as_deref().unwrap_or("-"));
+    /// # })
+    /// ```
+    pub async fn ticker_books_for(
+        &self,
+        markets: impl IntoIterator<Item = impl Into<MarketPair>>,
+    ) -> Result<Vec<TickerBook>> {
+        let markets: Vec<MarketPair> = markets.into_iter().map(Into::into).collect();
+
+        let books: HashMap<_, _> = self
+            .ticker_books()
+            .await?
+            .into_iter()
+            .filter_map(|book| Some((book.market.clone()?, book)))
+            .collect();
+
+        let mut found = Vec::with_capacity(markets.len());
+        let mut unknown = Vec::new();
+
+        for market in markets {
+            match books.get(market.as_str()) {
+                Some(book) => found.push(book.clone()),
+                None => unknown.push(market.to_string()),
+            }
+        }
+
+        if !unknown.is_empty() {
+            return Err(Error::UnknownMarkets(unknown));
+        }
+
+        Ok(found)
+    }
+
+    /// Retrieve the highest buy and lowest sell prices currently available for a given market.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let tb = c.ticker_book("BTC-EUR").await.unwrap();
+    ///
+    /// println!("Highest buy price for BTC-EUR: {}", tb.ask.unwrap());
+    /// # })
+    /// ```
+    pub async fn ticker_book(&self, market: impl Into<MarketPair>) -> Result<TickerBook> {
+        let market = market.into();
+        let request = self.get(self.url(&["ticker", "book"], &[("market", market.to_string())]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Retrieve high, low, open, last, and volume information for trades for all markets over the previous 24h.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let t24h = c.tickers_24h().await.unwrap();
+    ///
+    /// println!("Number of tickers: {}", t24h.len());
+    /// # })
+    /// ```
+    pub async fn tickers_24h(&self) -> Result<Vec<Ticker24h>> {
+        let request = self.get(self.url(&["ticker", "24h"], &[]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Get the `n` 24h tickers with the highest volume in the quote asset, highest first.
+    ///
+    /// Tickers without a quote volume are sorted last.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let top = c.top_by_volume(10).await.unwrap();
+    ///
+    /// for t24h in top {
+    ///     println!("{}: {}", t24h.market, t24h.volume_quote.unwrap_or_default());
+    /// }
+    /// # })
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub async fn top_by_volume(&self, n: usize) -> Result<Vec<Ticker24h>> {
+        let mut tickers = self.tickers_24h().await?;
+
+        tickers.sort_by_cached_key(|ticker| {
+            let volume = ticker.volume_quote.as_deref();
+            std::cmp::Reverse(volume.and_then(types::parse_decimal))
+        });
+        tickers.truncate(n);
+
+        Ok(tickers)
+    }
+
+    /// Retrieve high, low, open, last, and volume information for trades for a given market over the previous 24h.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let t24h = c.ticker_24h("BTC-EUR").await.unwrap();
+    ///
+    /// println!("24h ask for BTC-EUR: {}", t24h.ask.unwrap());
+    /// # })
+    /// ```
+    pub async fn ticker_24h(&self, market: impl Into<MarketPair>) -> Result<Ticker24h> {
+        let market = market.into();
+        let request = self.get(self.url(&["ticker", "24h"], &[("market", market.to_string())]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Like [`ticker_price`](Client::ticker_price), but also returning when the ticker was
+    /// received.
+    pub async fn ticker_price_timed(
+        &self,
+        pair: impl Into<MarketPair>,
+    ) -> Result<Timed<TickerPrice>> {
+        let value = self.ticker_price(pair).await?;
+        Ok(self.timed(value, None))
+    }
+
+    /// Like [`ticker_book`](Client::ticker_book), but also returning when the ticker was
+    /// received.
+    pub async fn ticker_book_timed(
+        &self,
+        market: impl Into<MarketPair>,
+    ) -> Result<Timed<TickerBook>> {
+        let value = self.ticker_book(market).await?;
+        Ok(self.timed(value, None))
+    }
+
+    /// Like [`ticker_24h`](Client::ticker_24h), but also returning when the ticker was received
+    /// and the time the exchange reports it is for, so stale tickers can be discarded.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let c = bitvavo::Client::new();
+    /// let t24h = c.ticker_24h_timed("BTC-EUR").await.unwrap();
+    ///
+    /// if t24h.age().is_some_and(|age| age < Duration::from_secs(1)) {
+    ///     println!("24h ask for BTC-EUR: {}", t24h.value.ask.unwrap());
+    /// }
+    /// # })
+    /// ```
+    pub async fn ticker_24h_timed(
+        &self,
+        market: impl Into<MarketPair>,
+    ) -> Result<Timed<Ticker24h>> {
+        let value = self.ticker_24h(market).await?;
+        let server_time = value.timestamp;
+        Ok(self.timed(value, server_time))
+    }
+
+    // Account endpoints
+
+    /// Retrieve information about the account.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let account = c.account().await.unwrap();
+    ///
+    /// println!("Fee for maker orders: {}", account.fees.maker);
+    /// # })
+    pub async fn account(&self) -> Result<Account> {
+        let request = self.signed_get(self.url(&["account"], &[]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Retrieve balances for all assets in the account.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let balances = c.balances().await.unwrap();
+    ///
+    /// println!("Number of assets held: {}", balances.len());
+    /// # })
+    pub async fn balances(&self) -> Result<Vec<Balance>> {
+        let request = self.signed_get(self.url(&["balance"], &[]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Retrieve balances for a particular asset in the account.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let balance = c.balance("BTC").await.unwrap();
+    ///
+    /// println!("BTC available: {}", balance.available);
+    /// # })
+    pub async fn balance(&self, symbol: impl Into<Symbol>) -> Result<Balance> {
+        let symbol = symbol.into();
+        let request = self.signed_get(self.url(&["balance"], &[("symbol", symbol.to_string())]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request::<Vec<Balance>>(http_response).await?;
+
+        Ok(response.into_iter().next().unwrap())
+    }
+
+    /// Compute the value of all balances in the account, expressed in the `quote` currency.
+    ///
+    /// Each asset held is valued at the price of its market against `quote`, and the balance of
+    /// `quote` itself is counted as is. Assets without a market against `quote` are skipped, and
+    /// do not contribute to the value. Balances and prices are fetched concurrently.
+    ///
+    /// Returns [`Error::InvalidNumber`] for the balance at which the value overflows.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let value = c.portfolio_value("EUR").await.unwrap();
+    ///
+    /// println!("Portfolio value: {value} EUR");
+    /// # })
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub async fn portfolio_value(&self, quote: impl Into<Symbol>) -> Result<Decimal> {
+        let quote = quote.into();
+        let (balances, prices) = futures_util::try_join!(self.balances(), self.ticker_prices())?;
+
+        let prices: std::collections::HashMap<_, _> = prices
+            .into_iter()
+            .filter_map(|ticker| Some((ticker.market, types::parse_decimal(&ticker.price?)?)))
+            .collect();
+
+        let mut value = Decimal::ZERO;
+
+        for balance in balances {
+            let total = match balance.total() {
+                Some(total) if !total.is_zero() => total,
+                _ => continue,
+            };
+
+            let worth = if balance.symbol == quote.as_str() {
+                Some(total)
+            } else {
+                let market = format!("{}-{quote}", balance.symbol);
+                match prices.get(&market) {
+                    Some(price) => total.checked_mul(*price),
+                    None => continue,
+                }
+            };
+
+            value = worth
+                .and_then(|worth| value.checked_add(worth))
+                .ok_or_else(|| Error::InvalidNumber {
+                    field: "balance",
+                    value: format!("{total} {}", balance.symbol),
+                })?;
+        }
+
+        Ok(value)
+    }
+
+    /// Get the fees that are charged for trading in a particular market. If no market is provided,
+    /// the fees for catrgory B are returned.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let fees = c.fees(None).await.unwrap();
+    ///
+    /// println!("Taker fee for category B: {}", fees.taker);
+    /// # })
+    pub async fn fees(&self, market: Option<MarketPair>) -> Result<Fees> {
+        let mut query = Vec::new();
+
+        if let Some(market) = market {
+            query.push(("market", market.to_string()));
+        }
+
+        let request = self.signed_get(self.url(&["account", "fees"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Get the maker and taker fee rates that apply to the account when trading in a market, at
+    /// its current fee tier, as `(maker, taker)`.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let (maker, taker) = c.effective_fees("BTC-EUR").await.unwrap();
+    ///
+    /// println!("Maker fee: {maker}, taker fee: {taker}");
+    /// # })
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub async fn effective_fees(
+        &self,
+        market: impl Into<MarketPair>,
+    ) -> Result<(Decimal, Decimal)> {
+        let market = market.into();
+        let fees = self.fees(Some(market)).await?;
+
+        let parse = |field, value: &str| {
+            types::parse_decimal(value).ok_or_else(|| Error::InvalidNumber {
+                field,
+                value: value.to_string(),
+            })
+        };
+
+        Ok((parse("maker", &fees.maker)?, parse("taker", &fees.taker)?))
+    }
+
+    // Transfer endpoints
+
+    /// Returns the deposit address or bank account information to increase the balance.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::DepositInfo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let deposit_info = c.deposit_info("BTC").await.unwrap();
+    ///
+    /// if let DepositInfo::Crypto(info) = deposit_info {
+    ///     println!("BTC deposit address: {}", info.address);
+    /// }
+    /// # })
+    /// ```
+    pub async fn deposit_info(&self, symbol: impl Into<Symbol>) -> Result<DepositInfo> {
+        let symbol = symbol.into();
+        let request = self.signed_get(self.url(&["deposit"], &[("symbol", symbol.to_string())]))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Returns the deposit history of the account.
+    ///
+    /// At most 1000 deposits are returned, and a larger `limit` is lowered to that.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Deposit;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let deposit_history = c.deposit_history(None, None, None, None).await.unwrap();
+    ///
+    /// println!("Number of deposits: {}", deposit_history.len());
+    /// # })
+    pub async fn deposit_history(
+        &self,
+        symbol: Option<Symbol>,
+        limit: Option<u64>,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<Vec<Deposit>> {
+        let mut query = Vec::new();
+
+        if let Some(symbol) = symbol {
+            query.push(("symbol", symbol.to_string()));
+        }
+        if let Some(limit) = limit {
+            let limit = LimitedEndpoint::DepositHistory.clamp(limit);
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(start) = start {
+            query.push(("start", start.to_string()));
+        }
+        if let Some(end) = end {
+            query.push(("end", end.to_string()));
+        }
+
+        let request = self.signed_get(self.url(&["depositHistory"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Find the deposit of an asset with the given transaction id, such as an on-chain
+    /// transaction hash.
+    ///
+    /// Bitvavo has no endpoint for a single deposit, so the deposit history of the asset is
+    /// fetched page by page and searched, going back no further than `since`, in milliseconds
+    /// since the epoch. Returns `None` if no deposit from then on has the transaction id.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let since = c.now_millis() - 30 * 24 * 60 * 60 * 1000;
+    /// let deposit = c.deposit("BTC", "0x1234", since).await.unwrap();
+    ///
+    /// if let Some(deposit) = deposit {
+    ///     println!("Status of the deposit: {:?}", deposit.status);
+    /// }
+    /// # })
+    /// ```
+    pub async fn deposit(
+        &self,
+        symbol: impl Into<Symbol>,
+        tx_id: &str,
+        since: u64,
+    ) -> Result<Option<Deposit>> {
+        let symbol = symbol.into();
+        let deposits = paginate(
+            Some(since),
+            None,
+            HISTORY_PAGE_LIMIT,
+            |deposit: &Deposit| deposit.timestamp,
+            |start, end, limit| self.deposit_history(Some(symbol.clone()), Some(limit), start, end),
+        )
+        .await?;
+
+        Ok(deposits
+            .into_iter()
+            .find(|deposit| deposit.tx_id.as_deref() == Some(tx_id)))
+    }
+
+    /// Withdraw an asset from the account to a given address.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::WithdrawOrder;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let response = c.withdraw(WithdrawOrder {
+    ///     symbol: String::from("BTC"),
+    ///     amount: String::from("0.1"),
+    ///     address: String::from("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+    ///     payment_id: None,
+    ///     internal: false,
+    ///     add_withdrawal_fee: false,
+    /// }).await.unwrap();
+    ///
+    /// println!("Withdrawal succeeded: {}", response.success);
+    /// # })
+    /// ```
+    pub async fn withdraw(&self, order: WithdrawOrder) -> Result<WithdrawalOrderResponse> {
+        let request = self.signed_post(self.url(&["withdrawal"], &[]), order)?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Returns the withdrawal history of the account.
+    ///
+    /// At most 1000 withdrawals are returned, and a larger `limit` is lowered to that.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Deposit;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let deposit_history = c.withdrawal_history(None, None, None, None).await.unwrap();
+    ///
+    /// println!("Number of withdrawals: {}", deposit_history.len());
+    /// # })
+    pub async fn withdrawal_history(
+        &self,
+        symbol: Option<Symbol>,
+        limit: Option<u64>,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> Result<Vec<Withdrawal>> {
+        let mut query = Vec::new();
+
+        if let Some(symbol) = symbol {
+            query.push(("symbol", symbol.to_string()));
+        }
+        if let Some(limit) = limit {
+            let limit = LimitedEndpoint::WithdrawalHistory.clamp(limit);
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(start) = start {
+            query.push(("start", start.to_string()));
+        }
+        if let Some(end) = end {
+            query.push(("end", end.to_string()));
+        }
+
+        let request = self.signed_get(self.url(&["withdrawalHistory"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Find the withdrawal of an asset with the given transaction id, such as an on-chain
+    /// transaction hash.
+    ///
+    /// Bitvavo has no endpoint for a single withdrawal, so the withdrawal history of the asset is
+    /// fetched page by page and searched, going back no further than `since`, in milliseconds
+    /// since the epoch. Returns `None` if no withdrawal from then on has the transaction id.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let since = c.now_millis() - 30 * 24 * 60 * 60 * 1000;
+    /// let withdrawal = c.withdrawal("BTC", "0x1234", since).await.unwrap();
+    ///
+    /// if let Some(withdrawal) = withdrawal {
+    ///     println!("Status of the withdrawal: {:?}", withdrawal.status);
+    /// }
+    /// # })
+    /// ```
+    pub async fn withdrawal(
+        &self,
+        symbol: impl Into<Symbol>,
+        tx_id: &str,
+        since: u64,
+    ) -> Result<Option<Withdrawal>> {
+        let symbol = symbol.into();
+        let withdrawals = paginate(
+            Some(since),
+            None,
+            HISTORY_PAGE_LIMIT,
+            |withdrawal: &Withdrawal| withdrawal.timestamp,
+            |start, end, limit| {
+                self.withdrawal_history(Some(symbol.clone()), Some(limit), start, end)
+            },
+        )
+        .await?;
+
+        Ok(withdrawals
+            .into_iter()
+            .find(|withdrawal| withdrawal.tx_id.as_deref() == Some(tx_id)))
+    }
+
+    // Trading endpoints
+
+    /// Places an order on the exchange.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::{Order, OrderType, TradeSide, TimeInForce};
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let response = c.place_order(Order {
+    ///     market: String::from("BTC-EUR"),
+    ///     side: TradeSide::Sell,
+    ///     order_type: OrderType::Limit,
+    ///     client_order_id: None,
+    ///     amount: Some(String::from("1")),
+    ///     amount_quote: None,
+    ///     price: Some(String::from("1000000")),
+    ///     trigger_amount: None,
+    ///     trigger_type: None,
+    ///     trigger_reference: None,
+    ///     time_in_force: Some(TimeInForce::ImmediateOrCancel),
+    ///     post_only: None,
+    ///     self_trade_prevention: None,
+    ///     disable_market_protection: false,
+    ///     response_required: false,
+    /// }).await.unwrap();
+    ///
+    /// println!("Order ID: {}", response.order_id);
+    /// # })
+    /// ```
+    pub async fn place_order(&self, order: Order) -> Result<OrderResponse> {
+        let request = self.signed_post(self.url(&["order"], &[]), order)?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Get the status of an order, including its fills.
+    ///
+    /// The order is referred to by an [`OrderRef`], or by the id the exchange assigned to it.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use uuid::Uuid;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let order_id = Uuid::parse_str("1be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+    /// let order = c.get_order("BTC-EUR", order_id).await.unwrap();
+    ///
+    /// println!("Number of fills: {}", order.fills.len());
+    /// # })
+    /// ```
+    pub async fn get_order(
+        &self,
+        market: impl Into<MarketPair>,
+        order: impl Into<OrderRef>,
+    ) -> Result<OrderStatus> {
+        let market = market.into();
+        let query = [("market", market.to_string()), order.into().query_param()];
+        let request = self.signed_get(self.url(&["order"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Cancel an open order.
+    ///
+    /// The order is referred to by an [`OrderRef`], or by the id the exchange assigned to it.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::OrderRef;
+    /// use uuid::Uuid;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let order_id = Uuid::parse_str("1be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+    /// c.cancel_order("BTC-EUR", order_id).await.unwrap();
+    ///
+    /// let client_order_id = Uuid::parse_str("2be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+    /// c.cancel_order("BTC-EUR", OrderRef::ByClientId(client_order_id)).await.unwrap();
+    /// # })
+    /// ```
+    pub async fn cancel_order(
+        &self,
+        market: impl Into<MarketPair>,
+        order: impl Into<OrderRef>,
+    ) -> Result<()> {
+        let market = market.into();
+        let query = [("market", market.to_string()), order.into().query_param()];
+        let request = self.signed_delete(self.url(&["order"], &query))?;
+
+        let http_response = request.send().await?;
+        response_unit(http_response).await?;
+
+        Ok(())
+    }
+
+    /// Poll an order every `poll_interval` until it is filled or otherwise done, returning its
+    /// final status.
+    ///
+    /// If the order is still open after `timeout`, it is cancelled, and its status after
+    /// cancelling is returned. The order may have been filled in the meantime, so check the
+    /// status rather than assuming it was cancelled. The interval is doubled while the remaining
+    /// rate limit is below the [warning threshold](Client::with_rate_limit_warning).
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use bitvavo_api as bitvavo;
+    /// use uuid::Uuid;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let order_id = Uuid::parse_str("1be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+    /// let order = c
+    ///     .wait_for_fill("BTC-EUR", order_id, Duration::from_millis(500), Duration::from_secs(30))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("Order ended as {:?}", order.status);
+    /// # })
+    /// ```
+    pub async fn wait_for_fill(
+        &self,
+        market: impl Into<MarketPair>,
+        order: impl Into<OrderRef>,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<OrderStatus> {
+        let market = &market.into();
+        let order = order.into();
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let status = self.get_order(market, order).await?;
+            if status.is_terminal() {
+                return Ok(status);
+            }
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+
+            let interval = match self.rate_limit_remaining() {
+                Some(remaining) if remaining < self.rate_limit_warning => poll_interval * 2,
+                _ => poll_interval,
+            };
+            tokio::time::sleep(interval.min(deadline - now)).await;
+        }
+
+        // An order that is done by now can no longer be found to cancel.
+        match self.cancel_order(market, order).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        self.get_order(market, order).await
+    }
+
+    /// Get the open orders of the account, optionally only those in a particular market.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let orders = c.orders_open(Some("BTC-EUR".into())).await.unwrap();
+    ///
+    /// println!("Number of open orders: {}", orders.len());
+    /// # })
+    /// ```
+    pub async fn orders_open(&self, market: Option<MarketPair>) -> Result<Vec<OrderStatus>> {
+        let mut query = Vec::new();
+        if let Some(market) = market {
+            query.push(("market", market.to_string()));
+        }
+
+        let request = self.signed_get(self.url(&["ordersOpen"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Cancel several orders in a market, reporting which were cancelled and which failed.
+    ///
+    /// The orders are cancelled concurrently, a few at a time. Failing to cancel one order, for
+    /// instance because it was filled in the meantime, does not stop the others from being
+    /// cancelled.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use uuid::Uuid;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let order_id = Uuid::parse_str("1be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+    /// let result = c.cancel_orders("BTC-EUR", [order_id]).await;
+    ///
+    /// for (order_id, err) in result.failed {
+    ///     println!("Failed to cancel {order_id}: {err}");
+    /// }
+    /// # })
+    /// ```
+    pub async fn cancel_orders(
+        &self,
+        market: impl Into<MarketPair>,
+        order_ids: impl IntoIterator<Item = Uuid>,
+    ) -> BulkCancelResult {
+        let market: &MarketPair = &market.into();
+
+        let results: Vec<_> = stream::iter(order_ids)
+            .map(|order_id| async move { (order_id, self.cancel_order(market, order_id).await) })
+            .buffer_unordered(CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+
+        let mut result = BulkCancelResult::default();
+
+        for (order_id, outcome) in results {
+            match outcome {
+                Ok(()) => result.cancelled.push(order_id),
+                Err(err) => result.failed.push((order_id, err)),
+            }
+        }
+
+        result
+    }
+
+    /// Cancel the open orders in a market that were created longer than `older_than` ago.
+    ///
+    /// The stale orders are cancelled using [`cancel_orders`](Client::cancel_orders), so the
+    /// result reports each of them as either cancelled or failed. An error is only returned if
+    /// the open orders cannot be listed.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use std::time::Duration;
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let result = c
+    ///     .cancel_stale_orders("BTC-EUR", Duration::from_secs(60))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("Cancelled {} orders", result.cancelled.len());
+    /// # })
+    /// ```
+    pub async fn cancel_stale_orders(
+        &self,
+        market: impl Into<MarketPair>,
+        older_than: Duration,
+    ) -> Result<BulkCancelResult> {
+        let market = market.into();
+        let now = self.now_millis();
+        let cutoff = now.saturating_sub(older_than.as_millis() as u64);
+
+        let orders = self.orders_open(Some(market.clone())).await?;
+        let stale = orders
+            .into_iter()
+            .filter(|order| order.created < cutoff)
+            .map(|order| order.order_id);
+
+        Ok(self.cancel_orders(market, stale).await)
+    }
+
+    /// Get the trades made by the account in a particular market.
+    ///
+    /// At most 1000 trades are returned, and a larger `limit` is lowered to that.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let trades = c.my_trades("BTC-EUR", None, None, None, None, None).await.unwrap();
+    ///
+    /// println!("Number of trades: {}", trades.len());
+    /// # })
+    /// ```
+    pub async fn my_trades(
+        &self,
+        market: impl Into<MarketPair>,
+        limit: Option<u64>,
+        start: Option<u64>,
+        end: Option<u64>,
+        trade_id_from: Option<String>,
+        trade_id_to: Option<String>,
+    ) -> Result<Vec<Fill>> {
+        let market = market.into();
+        let mut query = vec![("market", market.to_string())];
+
+        if let Some(limit) = limit {
+            let limit = LimitedEndpoint::MyTrades.clamp(limit);
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(start) = start {
+            query.push(("start", start.to_string()));
+        }
+        if let Some(end) = end {
+            query.push(("end", end.to_string()));
+        }
+        if let Some(trade_id_from) = trade_id_from {
+            query.push(("tradeIdFrom", trade_id_from));
+        }
+        if let Some(trade_id_to) = trade_id_to {
+            query.push(("tradeIdTo", trade_id_to));
+        }
+
+        let request = self.signed_get(self.url(&["trades"], &query))?;
+
+        let http_response = request.send().await?;
+        let response = response_from_request(http_response).await?;
+
+        Ok(response)
+    }
+
+    /// Get a page of the trades made by the account in a particular market, newest first.
+    ///
+    /// Cursors work like those of [`trades_paged`](Client::trades_paged), using the id of the
+    /// oldest trade in a page.
+    pub async fn my_trades_paged(
+        &self,
+        market: impl Into<MarketPair>,
+        limit: u64,
+        cursor: Option<String>,
+    ) -> Result<Page<Fill>> {
+        let limit = LimitedEndpoint::MyTrades.clamp(limit);
+        let trades = self
+            .my_trades(market, Some(limit), None, None, None, cursor.clone())
+            .await?;
+        Ok(page(trades, limit, cursor, |t| &t.id))
+    }
+
+    // Account history
+
+    /// Get the combined history of deposits, withdrawals and trades of the account, newest first.
+    ///
+    /// All pages in the requested time range are fetched. Trades are only included for the
+    /// markets listed in the options, since Bitvavo only reports them per market.
+    ///
+    /// ```no_run
+    /// # tokio_test::block_on(async {
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::TransactionHistoryOptions;
+    ///
+    /// let key = String::from("YOUR_API_KEY");
+    /// let secret = String::from("YOUR_API_SECRET");
+    ///
+    /// let c = bitvavo::Client::with_credentials(key, secret);
+    /// let history = c
+    ///     .transaction_history(TransactionHistoryOptions {
+    ///         markets: vec![String::from("BTC-EUR")],
+    ///         ..Default::default()
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// println!("Number of transactions: {}", history.len());
+    /// # })
+    /// ```
+    pub async fn transaction_history(
+        &self,
+        options: TransactionHistoryOptions,
+    ) -> Result<Vec<Transaction>> {
+        let TransactionHistoryOptions { start, end, .. } = options;
+        let mut transactions = Vec::new();
+
+        if options.includes(TransactionKind::Deposit) {
+            let deposits = paginate(
+                start,
+                end,
+                HISTORY_PAGE_LIMIT,
+                |deposit: &Deposit| deposit.timestamp,
+                |start, end, limit| self.deposit_history(None, Some(limit), start, end),
+            )
+            .await?;
+            transactions.extend(deposits.into_iter().map(Transaction::Deposit));
+        }
+
+        if options.includes(TransactionKind::Withdrawal) {
+            let withdrawals = paginate(
+                start,
+                end,
+                HISTORY_PAGE_LIMIT,
+                |withdrawal: &Withdrawal| withdrawal.timestamp,
+                |start, end, limit| self.withdrawal_history(None, Some(limit), start, end),
+            )
+            .await?;
+            transactions.extend(withdrawals.into_iter().map(Transaction::Withdrawal));
+        }
+
+        if options.includes(TransactionKind::Trade) {
+            for market in &options.markets {
+                let fills = paginate(
+                    start,
+                    end,
+                    HISTORY_PAGE_LIMIT,
+                    |fill: &Fill| fill.timestamp,
+                    |start, end, limit| self.my_trades(market, Some(limit), start, end, None, None),
+                )
+                .await?;
+                transactions.extend(fills.into_iter().map(Transaction::Trade));
+            }
+        }
+
+        transactions.sort_by_key(|transaction| std::cmp::Reverse(transaction.timestamp()));
+
+        Ok(transactions)
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicBool;
+
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn market_json(pair: &str, status: &str) -> serde_json::Value {
+        let (base, quote) = pair.split_once('-').unwrap();
+        fixtures::market(serde_json::json!({
+            "market": pair,
+            "status": status,
+            "base": base,
+            "quote": quote,
+        }))
+    }
+
+    async fn mock_markets(server: &MockServer, markets: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path("/v2/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(markets))
+            .mount(server)
+            .await;
+    }
+
+    fn mock_client(server: &MockServer) -> Client {
+        Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_time() {
+        let client = Client::new();
+        client
+            .time()
+            .await
+            .expect("Getting the time should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_time_with_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1 }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let err = client
+            .time_with_timeout(Duration::from_millis(50))
+            .await
+            .expect_err("Getting the time should time out");
+        assert!(err.is_timeout());
+
+        client
+            .time_with_timeout(Duration::from_secs(5))
+            .await
+            .expect("Getting the time within the timeout should succeed");
+    }
+
+    #[tokio::test]
+    async fn reqwest_error_names_endpoint() {
+        // Bind and immediately release a port, so nothing is listening on it.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = Client {
+            base_url: format!("http://{addr}").parse().unwrap(),
+            ..Client::new()
+        };
+
+        let err = client
+            .ticker_price("BTC-EUR")
+            .await
+            .expect_err("Connecting to a stopped server should fail");
+
+        match &err {
+            Error::Reqwest { endpoint, .. } => {
+                assert_eq!(endpoint, "/v2/ticker/price?market=BTC-EUR")
+            }
+            err => panic!("Expected a reqwest error, got {err:?}"),
+        }
+        assert!(err
+            .to_string()
+            .starts_with("reqwest error on /v2/ticker/price?market=BTC-EUR"));
+        assert!(err
+            .source()
+            .is_some_and(|source| source.is::<reqwest::Error>()));
+    }
+
+    #[tokio::test]
+    async fn get_with_raw() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/price"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "market": "BTC-EUR",
+                "price": "30000",
+                "extra": true,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let (ticker, raw) = client
+            .get_with_raw::<TickerPrice>("ticker/price?market=BTC-EUR")
+            .await
+            .expect("Getting the ticker with its raw JSON should succeed");
+
+        assert_eq!(ticker.market, "BTC-EUR");
+        assert_eq!(ticker.price.as_deref(), Some("30000"));
+        assert_eq!(raw["extra"], serde_json::Value::Bool(true));
+    }
+
+    #[tokio::test]
+    async fn get_with_raw_without_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/price"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "market": "BTC-EUR", "price": "30000" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v3/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::new()
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap();
+
+        let (ticker, _) = client
+            .get_with_raw::<TickerPrice>("ticker/price?market=BTC-EUR")
+            .await
+            .expect("Getting the ticker with its raw JSON should succeed");
+        assert_eq!(ticker.market, "BTC-EUR");
+
+        let (time, _) = client
+            .get_versioned_with_raw::<serde_json::Value>("v3", "time")
+            .await
+            .expect("Getting the time should succeed");
+        assert_eq!(time["time"], 1539180275424u64);
+
+        let err = client
+            .signed_get_with_raw::<serde_json::Value>("balance")
+            .await
+            .expect_err("Getting an account endpoint without credentials should fail");
+        assert!(matches!(err, Error::Unsupported(_)));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn query_parameters_are_encoded_and_signed() {
+        let client = Client::with_credentials("key".to_string(), "secret".to_string());
+
+        let url = client.url(
+            &["BTC-EUR", "trades"],
+            &[("tradeIdFrom", "a b&c".to_string())],
+        );
+        let request = client
+            .signed_get(url)
+            .expect("Building the request should succeed");
+
+        let slug = "/v2/BTC-EUR/trades?tradeIdFrom=a+b%26c";
+        assert_eq!(request.slug, slug);
+
+        let request = request
+            .builder
+            .build()
+            .expect("Building the request should succeed");
+        assert_eq!(
+            request.url().as_str(),
+            format!("https://api.bitvavo.com{slug}")
+        );
+
+        let header = |name| request.headers()[name].to_str().unwrap();
+        let timestamp = header("Bitvavo-Access-Timestamp");
+
+        let signature = HmacSha256Signer::new("secret".to_string())
+            .sign(timestamp, "GET", slug, "")
+            .unwrap();
+
+        assert_eq!(header("Bitvavo-Access-Signature"), signature);
+    }
+
+    #[test]
+    fn base_url_must_have_a_path() {
+        for url in ["mailto:api@bitvavo.com", "data:text/plain,bitvavo"] {
+            let err = Client::new()
+                .with_base_url(url.parse().unwrap())
+                .expect_err("Setting the base URL should fail");
+            assert!(matches!(err, Error::Unsupported(_)));
+        }
+    }
+
+    #[test]
+    fn base_url_path_is_not_signed() {
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url("https://proxy.example.com/bitvavo/".parse().unwrap())
+            .unwrap();
+
+        let url = client.url(&["time"], &[]);
+        let request = client
+            .signed_get(url)
+            .expect("Building the request should succeed");
+
+        assert_eq!(request.slug, "/v2/time");
+
+        let request = request
+            .builder
+            .build()
+            .expect("Building the request should succeed");
+        assert_eq!(
+            request.url().as_str(),
+            "https://proxy.example.com/bitvavo/v2/time"
+        );
+    }
+
+    #[tokio::test]
+    async fn api_version_is_configurable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v3/time"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "time": 1539180275424u64,
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).with_api_version("v3");
+        let time = client
+            .time()
+            .await
+            .expect("Getting the time should succeed");
+        assert_eq!(time, 1539180275424);
+
+        let client = mock_client(&server);
+        let (time, _) = client
+            .get_versioned_with_raw::<serde_json::Value>("v3", "time")
+            .await
+            .expect("Getting the time should succeed");
+        assert_eq!(time["time"], 1539180275424u64);
+
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_api_version("v3");
+        let request = client
+            .signed_get(client.url(&["time"], &[]))
+            .expect("Building the request should succeed");
+        assert_eq!(request.slug, "/v3/time");
+    }
+
+    #[tokio::test]
+    async fn get_assets() {
+        let client = Client::new();
+        client
+            .assets()
+            .await
+            .expect("Getting the assets should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_asset() {
+        let client = Client::new();
+        client
+            .asset("BTC")
+            .await
+            .expect("Getting the asset should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_markets() {
+        let client = Client::new();
+        client
+            .markets()
+            .await
+            .expect("Getting the markets should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_market() {
+        let client = Client::new();
+        client
+            .market("BTC-EUR")
+            .await
+            .expect("Getting the market should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_market_index() {
+        let server = MockServer::start().await;
+        mock_markets(
+            &server,
+            serde_json::json!([
+                market_json("XRP-EUR", "trading"),
+                market_json("BTC-EUR", "halted"),
+            ]),
+        )
+        .await;
+
+        let markets = mock_client(&server)
+            .market_index()
+            .await
+            .expect("Getting the market index should succeed");
+
+        assert_eq!(markets.len(), 2);
+        assert_eq!(markets["BTC-EUR"].status, MarketStatus::Halted);
+        assert_eq!(markets["XRP-EUR"].status, MarketStatus::Trading);
+    }
+
+    #[tokio::test]
+    async fn get_markets_sorted() {
+        let server = MockServer::start().await;
+        mock_markets(
+            &server,
+            serde_json::json!([
+                market_json("XRP-EUR", "trading"),
+                market_json("BTC-EUR", "trading"),
+                market_json("ETH-EUR", "trading"),
+            ]),
+        )
+        .await;
+
+        let client = mock_client(&server);
+
+        let markets = client
+            .markets()
+            .await
+            .expect("Getting the markets should succeed");
+        let pairs: Vec<_> = markets.iter().map(|m| m.pair.as_str()).collect();
+        assert_eq!(pairs, ["XRP-EUR", "BTC-EUR", "ETH-EUR"]);
+
+        let markets = client
+            .markets_sorted()
+            .await
+            .expect("Getting the sorted markets should succeed");
+        let pairs: Vec<_> = markets.iter().map(|m| m.pair.as_str()).collect();
+        assert_eq!(pairs, ["BTC-EUR", "ETH-EUR", "XRP-EUR"]);
+    }
+
+    #[tokio::test]
+    async fn get_markets_with_status() {
+        let server = MockServer::start().await;
+        mock_markets(
+            &server,
+            serde_json::json!([
+                market_json("BTC-EUR", "trading"),
+                market_json("ETH-EUR", "halted"),
+                market_json("XRP-EUR", "trading"),
+            ]),
+        )
+        .await;
+
+        let client = mock_client(&server);
+
+        let tradable = client
+            .markets_tradable()
+            .await
+            .expect("Getting the tradable markets should succeed");
+        let pairs: Vec<_> = tradable.iter().map(|m| m.pair.as_str()).collect();
+        assert_eq!(pairs, ["BTC-EUR", "XRP-EUR"]);
+
+        let halted = client
+            .markets_with_status(MarketStatus::Halted)
+            .await
+            .expect("Getting the halted markets should succeed");
+        let pairs: Vec<_> = halted.iter().map(|m| m.pair.as_str()).collect();
+        assert_eq!(pairs, ["ETH-EUR"]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_markets_share_a_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/markets"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([market_json("BTC-EUR", "trading")]))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let requests = || async { server.received_requests().await.unwrap().len() };
+
+        let calls = (0..8).map(|_| {
+            let client = client.clone();
+            async move { client.markets().await }
+        });
+        for markets in futures_util::future::join_all(calls).await {
+            let markets = markets.expect("Getting the markets should succeed");
+            assert_eq!(markets.len(), 1);
+        }
+        assert_eq!(requests().await, 1);
+
+        // Once the request has completed, its result is not reused.
+        client
+            .markets()
+            .await
+            .expect("Getting the markets should succeed");
+        assert_eq!(requests().await, 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_markets_share_an_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/markets"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .set_body_json(serde_json::json!({
+                        "errorCode": 105,
+                        "error": "Rate limit exceeded.",
+                    }))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let calls = (0..8).map(|_| {
+            let client = client.clone();
+            async move { client.markets().await }
+        });
+        for markets in futures_util::future::join_all(calls).await {
+            let err = markets.expect_err("Getting the markets should fail");
+            assert!(
+                matches!(&err, Error::Shared(err) if matches!(**err, Error::Bitvavo { code: 105, .. }))
+            );
+            assert_eq!(err.kind(), ErrorKind::RateLimited);
+        }
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // Without anyone to share it with, the error is returned as is.
+        let err = client
+            .markets()
+            .await
+            .expect_err("Getting the markets should fail");
+        assert!(matches!(err, Error::Bitvavo { code: 105, .. }));
+    }
+
+    #[tokio::test]
+    async fn dropped_markets_call_does_not_strand_waiters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/markets"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([market_json("BTC-EUR", "trading")]))
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        // The first call makes the request, but gives up on it before it completes.
+        let first = tokio::time::timeout(Duration::from_millis(50), client.markets());
+        let second = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            client.markets().await
+        };
+        let (first, second) = tokio::join!(first, second);
+
+        assert!(first.is_err());
+        let markets = second.expect("Getting the markets should succeed");
+        assert_eq!(markets.len(), 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_currencies() {
+        let server = MockServer::start().await;
+        mock_markets(
+            &server,
+            serde_json::json!([
+                market_json("BTC-EUR", "trading"),
+                market_json("ETH-BTC", "trading"),
+                market_json("ETH-EUR", "halted"),
+                market_json("BTC-USDC", "trading"),
+            ]),
+        )
+        .await;
+
+        let client = mock_client(&server);
+
+        let quotes = client
+            .quote_currencies()
+            .await
+            .expect("Getting the quote currencies should succeed");
+        assert_eq!(Vec::from_iter(quotes), ["BTC", "EUR", "USDC"]);
+
+        let bases = client
+            .base_currencies()
+            .await
+            .expect("Getting the base currencies should succeed");
+        assert_eq!(Vec::from_iter(bases), ["BTC", "ETH"]);
+    }
+
+    #[tokio::test]
+    async fn get_markets_supporting() {
+        let server = MockServer::start().await;
+        mock_markets(
+            &server,
+            serde_json::json!([
+                fixtures::market(serde_json::json!({
+                    "market": "BTC-EUR",
+                    "orderTypes": ["market", "limit"],
+                })),
+                fixtures::market(serde_json::json!({
+                    "market": "ETH-EUR",
+                    "orderTypes": ["market", "limit", "stopLoss", "stopLossLimit"],
+                })),
+                fixtures::market(serde_json::json!({
+                    "market": "XRP-EUR",
+                    "orderTypes": ["market", "limit", "takeProfit", "stopLoss"],
+                })),
+                fixtures::market(serde_json::json!({
+                    "market": "ADA-EUR",
+                    "orderTypes": ["limit"],
+                })),
+            ]),
+        )
+        .await;
+
+        let client = mock_client(&server);
+
+        let pairs = client
+            .markets_supporting(OrderType::StopLoss)
+            .await
+            .expect("Getting the markets should succeed");
+        assert_eq!(pairs, ["ETH-EUR", "XRP-EUR"]);
+
+        let pairs = client
+            .markets_supporting(OrderType::Market)
+            .await
+            .expect("Getting the markets should succeed");
+        assert_eq!(pairs, ["BTC-EUR", "ETH-EUR", "XRP-EUR"]);
+
+        let pairs = client
+            .markets_supporting(OrderType::TakeProfitLimit)
+            .await
+            .expect("Getting the markets should succeed");
+        assert!(pairs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_asset_decimals() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/assets"))
+            .and(query_param("symbol", "XYZ"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "errorCode": 205,
+                "error": "symbol parameter is invalid."
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/assets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                fixtures::asset(serde_json::json!({ "symbol": "BTC", "decimals": 8 })),
+                fixtures::asset(serde_json::json!({ "symbol": "EUR", "decimals": 2 })),
+            ])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        // The first call fetches all the assets, and the rest are served from the cache.
+        for (symbol, expected) in [("BTC", 8), ("EUR", 2), ("BTC", 8)] {
+            let decimals = client
+                .asset_decimals(symbol)
+                .await
+                .expect("Getting the decimals should succeed");
+            assert_eq!(decimals, expected, "{symbol}");
+        }
+
+        // An unknown asset fetches the assets again, and then itself for the API's error.
+        let err = client
+            .asset_decimals("XYZ")
+            .await
+            .expect_err("Getting the decimals of an unknown asset should fail");
+        assert!(matches!(err, Error::Bitvavo { code: 205, .. }));
+
+        #[cfg(feature = "decimal")]
+        {
+            let amount = "1.239".parse().unwrap();
+            let formatted = client
+                .format_amount("EUR", amount)
+                .await
+                .expect("Formatting the amount should succeed");
+            assert_eq!(formatted, "1.23");
+
+            let rounding = Rounding {
+                amount: RoundingMode::NearestEven,
+                ..Rounding::default()
+            };
+            let formatted = client
+                .format_amount_with("BTC", amount, rounding)
+                .await
+                .expect("Formatting the amount should succeed");
+            assert_eq!(formatted, "1.23900000");
+        }
+    }
+
+    #[tokio::test]
+    async fn get_ticker_books_for() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/book"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                fixtures::ticker_book(serde_json::json!({ "market": "BTC-EUR", "bid": "30000" })),
+                fixtures::ticker_book(serde_json::json!({ "market": "ETH-EUR", "bid": "2000" })),
+                fixtures::ticker_book(serde_json::json!({ "market": "XRP-EUR", "bid": "0.5" })),
+            ])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let books = client
+            .ticker_books_for(["XRP-EUR", "BTC-EUR"])
+            .await
+            .expect("Getting the ticker books should succeed");
+        let bids: Vec<_> = books.iter().map(|book| book.bid.as_deref()).collect();
+        assert_eq!(bids, [Some("0.5"), Some("30000")]);
+
+        let err = client
+            .ticker_books_for(["BTC-EUR", "BAD-EUR", "ETH-EUR", "WORSE-EUR"])
+            .await
+            .expect_err("Getting unknown markets should fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(
+            matches!(err, Error::UnknownMarkets(markets) if markets == ["BAD-EUR", "WORSE-EUR"])
+        );
+    }
+
+    #[tokio::test]
+    async fn get_order_book() {
+        let client = Client::new();
+        client
+            .order_book("BTC-EUR", Some(2))
+            .await
+            .expect("Getting the order book should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_trades() {
+        let client = Client::new();
+        client
+            .trades("BTC-EUR", None, None, None, None, None)
+            .await
+            .expect("Getting the order book should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_candles() {
+        let client = Client::new();
+        client
+            .candles("BTC-EUR", CandleInterval::OneDay, Some(1), None, None)
+            .await
+            .expect("Getting the candles should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_ticker_prices() {
+        let client = Client::new();
+        client
+            .ticker_prices()
+            .await
+            .expect("Getting the markets should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_ticker_price() {
+        let client = Client::new();
+        client
+            .ticker_price("BTC-EUR")
+            .await
+            .expect("Getting the market should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_ticker_books() {
+        let client = Client::new();
+        client
+            .ticker_books()
+            .await
+            .expect("Getting the ticker books should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_ticker_book() {
+        let client = Client::new();
+        client
+            .ticker_book("BTC-EUR")
+            .await
+            .expect("Getting the ticker book should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_tickers_24h() {
+        let client = Client::new();
+        client
+            .tickers_24h()
+            .await
+            .expect("Getting the 24h tickers should succeed");
+    }
+
+    #[tokio::test]
+    async fn get_ticker_24h() {
+        let client = Client::new();
+        client
+            .ticker_24h("BTC-EUR")
+            .await
+            .expect("Getting the 24h tickers should succeed");
+    }
+
+    #[cfg(feature = "decimal")]
+    #[tokio::test]
+    async fn top_by_volume() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/24h"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "market": "ETH-EUR", "volumeQuote": "900.5" },
+                { "market": "NEW-EUR", "volumeQuote": null },
+                { "market": "BTC-EUR", "volumeQuote": "12000" },
+                { "market": "XRP-EUR", "volumeQuote": "80" },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let top = client
+            .top_by_volume(2)
+            .await
+            .expect("Getting the top tickers should succeed");
+        let markets: Vec<_> = top.iter().map(|t| t.market.as_str()).collect();
+        assert_eq!(markets, ["BTC-EUR", "ETH-EUR"]);
+
+        let all = client
+            .top_by_volume(10)
+            .await
+            .expect("Getting the top tickers should succeed");
+        let markets: Vec<_> = all.iter().map(|t| t.market.as_str()).collect();
+        assert_eq!(markets, ["BTC-EUR", "ETH-EUR", "XRP-EUR", "NEW-EUR"]);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[tokio::test]
+    async fn get_portfolio_value() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "symbol": "EUR", "available": "100", "inOrder": "50" },
+                { "symbol": "BTC", "available": "0.5", "inOrder": "0.5" },
+                { "symbol": "ETH", "available": "0", "inOrder": "0" },
+                { "symbol": "FOO", "available": "10", "inOrder": "0" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/price"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "market": "BTC-EUR", "price": "30000" },
+                { "market": "ETH-EUR", "price": "2000" },
+                { "market": "BTC-USDC", "price": "32000" },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let value = client
+            .portfolio_value("EUR")
+            .await
+            .expect("Getting the portfolio value should succeed");
+
+        assert_eq!(value, Decimal::from(30150));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[tokio::test]
+    async fn portfolio_value_overflow() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "symbol": "BTC", "available": "79228162514264337593543950335", "inOrder": "0" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/price"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                { "market": "BTC-EUR", "price": "30000" },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let err = client
+            .portfolio_value("EUR")
+            .await
+            .expect_err("Getting the portfolio value should fail");
+
+        assert!(matches!(
+            err,
+            Error::InvalidNumber {
+                field: "balance",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn paginate_over_time() {
+        // Timestamps of the items on the "server", newest first.
+        let all = [9u64, 8, 7, 7, 7, 5, 4, 2, 1];
+
+        // Serve pages as the API would, treating `end` as inclusive.
+        let fetch = |start: Option<u64>, end: Option<u64>, limit: u64| {
+            let page: Vec<u64> = all
+                .iter()
+                .copied()
+                .filter(|t| start.is_none_or(|s| *t >= s))
+                .filter(|t| end.is_none_or(|e| *t <= e))
+                .take(limit as usize)
+                .collect();
+            async move { Ok(page) }
+        };
+
+        let items = paginate(None, None, 3, |t: &u64| *t, fetch)
+            .await
+            .expect("Paginating should succeed");
+        assert_eq!(items, all);
+
+        let items = paginate(Some(4), Some(8), 3, |t: &u64| *t, fetch)
+            .await
+            .expect("Paginating should succeed");
+        assert_eq!(items, [8, 7, 7, 7, 5, 4]);
+    }
+
+    #[tokio::test]
+    async fn find_deposit_by_tx_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/depositHistory"))
+            .and(query_param("symbol", "BTC"))
+            .and(query_param("start", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                fixtures::transfer(serde_json::json!({ "timestamp": 20, "txId": "0xabc" })),
+                fixtures::transfer(serde_json::json!({ "timestamp": 10, "txId": "0xdef" })),
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let found = client
+            .deposit("BTC", "0xdef", 5)
+            .await
+            .expect("Finding the deposit should succeed")
+            .expect("The deposit should be found");
+        assert_eq!(found.timestamp, 10);
+
+        let missing = client
+            .deposit("BTC", "0x123", 5)
+            .await
+            .expect("Finding the deposit should succeed");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn find_withdrawal_by_tx_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/withdrawalHistory"))
+            .and(query_param("symbol", "BTC"))
+            .and(query_param("start", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                fixtures::transfer(serde_json::json!({ "timestamp": 20, "txId": "0xabc" })),
+                fixtures::transfer(serde_json::json!({ "timestamp": 10, "txId": "0xdef" })),
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let found = client
+            .withdrawal("BTC", "0xdef", 5)
+            .await
+            .expect("Finding the withdrawal should succeed")
+            .expect("The withdrawal should be found");
+        assert_eq!(found.timestamp, 10);
+
+        let missing = client
+            .withdrawal("BTC", "0x123", 5)
+            .await
+            .expect("Finding the withdrawal should succeed");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_transaction_history() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/depositHistory"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "timestamp": 30,
+                    "symbol": "EUR",
+                    "amount": "100",
+                    "fee": "0",
+                    "status": "completed",
+                },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/withdrawalHistory"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "timestamp": 10,
+                    "symbol": "BTC",
+                    "amount": "0.1",
+                    "fee": "0.0001",
+                    "status": "completed",
+                },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/trades"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": "108c3633-0276-4480-a902-17a01829deae",
+                    "orderId": "1d671998-3d44-4df4-965f-0d48bd129a1b",
+                    "timestamp": 20,
+                    "market": "BTC-EUR",
+                    "side": "buy",
+                    "amount": "0.1",
+                    "price": "30000",
+                    "taker": true,
+                    "fee": "7.5",
+                    "feeCurrency": "EUR",
+                    "settled": true,
+                },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let history = client
+            .transaction_history(TransactionHistoryOptions {
+                markets: vec![String::from("BTC-EUR")],
+                ..Default::default()
+            })
+            .await
+            .expect("Getting the transaction history should succeed");
+
+        let kinds: Vec<_> = history.iter().map(Transaction::kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                TransactionKind::Deposit,
+                TransactionKind::Trade,
+                TransactionKind::Withdrawal
+            ]
+        );
+
+        let history = client
+            .transaction_history(TransactionHistoryOptions {
+                kinds: vec![TransactionKind::Withdrawal],
+                markets: vec![String::from("BTC-EUR")],
+                ..Default::default()
+            })
+            .await
+            .expect("Getting the transaction history should succeed");
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind(), TransactionKind::Withdrawal);
+    }
+
+    #[tokio::test]
+    async fn get_candles_enveloped() {
+        let candle = serde_json::json!([1548684000000u64, "3000", "3100", "2900", "3050", "12.5"]);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("interval", "1h"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([candle])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("interval", "1d"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "market": "BTC-EUR",
+                "interval": "1d",
+                "candles": [candle]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        for interval in [CandleInterval::OneHour, CandleInterval::OneDay] {
+            let candles = client
+                .candles("BTC-EUR", interval, None, None, None)
+                .await
+                .expect("Getting the candles should succeed");
+            assert_eq!(candles.len(), 1);
+            assert_eq!(candles[0].time, 1548684000000);
+            assert_eq!(candles[0].close, "3050");
+        }
+    }
+
+    #[tokio::test]
+    async fn get_candles_multi() {
+        let server = MockServer::start().await;
+        for interval in ["1m", "1h"] {
+            Mock::given(method("GET"))
+                .and(path("/v2/BTC-EUR/candles"))
+                .and(query_param("interval", interval))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!([[
+                        1548684000000u64,
+                        "3000",
+                        "3100",
+                        "2900",
+                        "3050",
+                        "12.5"
+                    ],])),
+                )
+                .mount(&server)
+                .await;
+        }
+        for interval in ["5m", "1d"] {
+            Mock::given(method("GET"))
+                .and(path("/v2/BTC-EUR/candles"))
+                .and(query_param("interval", interval))
+                .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "errorCode": 205,
+                    "error": "interval parameter is invalid."
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        let client = mock_client(&server);
+
+        let candles = client
+            .candles_multi(
+                "BTC-EUR",
+                &[CandleInterval::OneMinute, CandleInterval::OneHour],
+                Some(1),
+            )
+            .await
+            .expect("Getting the candles should succeed");
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles["1m"].len(), 1);
+        assert_eq!(candles["1h"].len(), 1);
+
+        let err = client
+            .candles_multi(
+                "BTC-EUR",
+                &[
+                    CandleInterval::OneMinute,
+                    CandleInterval::FiveMinutes,
+                    CandleInterval::OneDay,
+                ],
+                Some(1),
+            )
+            .await
+            .expect_err("Getting the candles should fail");
+        assert!(matches!(err, Error::Multiple(errs) if errs.len() == 2));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[tokio::test]
+    async fn get_candles_for_local_day() {
+        // The day daylight saving time ends in Amsterdam, 25 hours from 22:00 UTC.
+        let start = 1729980000000u64;
+        let end = 1730070000000u64;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("interval", "1h"))
+            .and(query_param("limit", "25"))
+            .and(query_param("start", start.to_string()))
+            .and(query_param("end", end.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                [end, "1", "1", "1", "1", "1"],
+                [end - 3600000, "1", "1", "1", "1", "1"],
+                [start, "1", "1", "1", "1", "1"],
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 10, 27).unwrap();
+        let candles = client
+            .candles_for_local_day(
+                "BTC-EUR",
+                CandleInterval::OneHour,
+                chrono_tz::Europe::Amsterdam,
+                date,
+            )
+            .await
+            .expect("Getting the candles should succeed");
+
+        let times: Vec<_> = candles.iter().map(|c| c.time).collect();
+        assert_eq!(times, [end - 3600000, start]);
+
+        let date = chrono::NaiveDate::from_ymd_opt(1969, 12, 30).unwrap();
+        let err = client
+            .candles_for_local_day(
+                "BTC-EUR",
+                CandleInterval::OneHour,
+                chrono_tz::Europe::Amsterdam,
+                date,
+            )
+            .await
+            .expect_err("Getting the candles of a day before 1970 should fail");
+        assert!(matches!(err, Error::DateOutOfRange(d) if d == date));
+        assert_eq!(err.kind(), ErrorKind::Config);
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[tokio::test]
+    async fn get_candles_for_long_local_day() {
+        // The day daylight saving time ends in Amsterdam has 1500 minutes, more than fit in one
+        // page of candles.
+        let start = 1729980000000u64;
+        let end = 1730070000000u64;
+        let minute = 60000;
+
+        // The API includes the candle at `end`, and returns candles newest first.
+        let candles = |from: u64, to: u64| -> serde_json::Value {
+            (from..=to)
+                .rev()
+                .step_by(minute as usize)
+                .map(|time| serde_json::json!([time, "1", "1", "1", "1", "1"]))
+                .collect()
+        };
+        let oldest_of_first_page = end - 1439 * minute;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("limit", "1440"))
+            .and(query_param("end", end.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(candles(oldest_of_first_page, end)),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("limit", "1440"))
+            .and(query_param("end", oldest_of_first_page.to_string()))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(candles(start, oldest_of_first_page)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 10, 27).unwrap();
+        let candles = client
+            .candles_for_local_day(
+                "BTC-EUR",
+                CandleInterval::OneMinute,
+                chrono_tz::Europe::Amsterdam,
+                date,
+            )
+            .await
+            .expect("Getting the candles should succeed");
+
+        assert_eq!(candles.len(), 1500);
+        assert_eq!(candles.first().map(|c| c.time), Some(end - minute));
+        assert_eq!(candles.last().map(|c| c.time), Some(start));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn public_endpoints_are_not_signed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap();
+        client
+            .time()
+            .await
+            .expect("Getting the time should succeed");
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0]
+            .headers
+            .keys()
+            .all(|name| !name.as_str().starts_with("bitvavo-access")));
+    }
+
+    #[tokio::test]
+    async fn only_account_endpoints_are_signed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/price"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "market": "BTC-EUR", "price": "30000" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap();
+        client
+            .ticker_price("BTC-EUR")
+            .await
+            .expect("Getting the ticker should succeed");
+        client
+            .get_with_raw::<TickerPrice>("ticker/price?market=BTC-EUR")
+            .await
+            .expect("Getting the ticker with its raw JSON should succeed");
+        client
+            .balances()
+            .await
+            .expect("Getting the balances should succeed");
+
+        let requests = server.received_requests().await.unwrap();
+        let signed = |i: usize| {
+            requests[i]
+                .headers
+                .keys()
+                .any(|name| name.as_str().starts_with("bitvavo-access"))
+        };
+        assert!(!signed(0));
+        assert!(!signed(1));
+        assert!(signed(2));
+    }
+
+    #[tokio::test]
+    async fn get_trades_paged() {
+        let trade = |id: &str| fixtures::trade(serde_json::json!({ "id": id }));
+
+        let server = MockServer::start().await;
+        // The endpoint is assumed to possibly include the trade at the cursor.
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/trades"))
+            .and(query_param("tradeIdTo", "d"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([trade("d"), trade("c"),])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/trades"))
+            .and(query_param("tradeIdTo", "c"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([trade("a")])))
+            .mount(&server)
+            .await;
+
+        // Without a cursor, the newest trades are returned.
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/trades"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([trade("e"), trade("d")])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let mut ids = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = client
+                .trades_paged("BTC-EUR", 2, cursor)
+                .await
+                .expect("Getting a page of trades should succeed");
+            ids.extend(page.items.into_iter().map(|t| t.id));
+
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(ids, ["e", "d", "c", "a"]);
+    }
+
+    #[tokio::test]
+    async fn signed_request_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "symbol": "BTC",
+                    "available": "1.5",
+                    "inOrder": "0.5",
+                }])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::with_credentials("key".to_string(), "bitvavo".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap()
+            .with_time_source(|| 1548172481125);
+        client
+            .balance("BTC")
+            .await
+            .expect("Getting the balance should succeed");
+
+        let requests = server.received_requests().await.unwrap();
+        let mut headers: Vec<_> = requests[0]
+            .headers
+            .iter()
+            .filter(|(name, _)| name.as_str().starts_with("bitvavo-"))
+            .map(|(name, values)| (name.as_str().to_string(), values.as_str().to_string()))
+            .collect();
+        headers.sort();
+
+        assert_eq!(
+            headers,
+            [
+                ("bitvavo-access-key", "key"),
+                (
+                    "bitvavo-access-signature",
+                    "6a763cc043fc255f25c040d1b6f8a5ff9ca2c7a96eaf26ce6cbebba3fd1c3160"
+                ),
+                ("bitvavo-access-timestamp", "1548172481125"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn decode_hand_written_visitors() {
+        let book: OrderBook = decode(
+            br#"{"market":"BTC-EUR","nonce":1,"bids":[["100","1"],["99","3"]],"asks":[["101","2"]]}"#,
+        )
+        .expect("Decoding the order book should succeed");
+        assert_eq!(book.depth(), (2, 1));
+        assert_eq!(book.bids[1].price, "99");
+
+        let candles: Vec<OHLCV> =
+            decode(br#"[[1548684000000, "3000", "3100", "2900", "3050", "12.5"]]"#)
+                .expect("Decoding the candles should succeed");
+        assert_eq!(candles[0].close, "3050");
+
+        let fees: Fees = decode(br#"{"tier":0,"volume":10000,"taker":0.0025,"maker":"0.0015"}"#)
+            .expect("Decoding the fees should succeed");
+        assert_eq!(fees.taker, "0.0025");
+
+        let err = decode::<Fees>(b"{").expect_err("Decoding truncated JSON should fail");
+        assert_eq!(err.kind(), ErrorKind::Decode);
+    }
+
+    #[test]
+    fn error_kinds() {
+        let bitvavo = |code| Error::Bitvavo {
+            code,
+            message: String::new(),
+        };
+
+        assert_eq!(
+            Error::from(serde_json::from_str::<u64>("").unwrap_err()).kind(),
+            ErrorKind::Decode
+        );
+        assert_eq!(
+            Error::InvalidNumber {
+                field: "close",
+                value: String::from("NaN"),
+            }
+            .kind(),
+            ErrorKind::Decode
+        );
+        assert_eq!(
+            Error::NonceMismatch {
+                expected: 2,
+                received: 3,
+            }
+            .kind(),
+            ErrorKind::Decode
+        );
+        assert_eq!(bitvavo(105).kind(), ErrorKind::RateLimited);
+        assert_eq!(bitvavo(110).kind(), ErrorKind::NotFound);
+        assert_eq!(bitvavo(240).kind(), ErrorKind::NotFound);
+        assert_eq!(bitvavo(309).kind(), ErrorKind::Auth);
+        assert_eq!(bitvavo(205).kind(), ErrorKind::Api);
+        assert_eq!(
+            Error::from(hex::FromHexError::OddLength).kind(),
+            ErrorKind::Config
+        );
+        assert_eq!(
+            Error::InvalidOrder(InvalidOrder::MissingAmount).kind(),
+            ErrorKind::Config
+        );
+        assert_eq!(
+            Error::Multiple(vec![bitvavo(309), bitvavo(205)]).kind(),
+            ErrorKind::Auth
+        );
+    }
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+    #[tokio::test]
+    async fn rejected_signature_and_timestamp() {
+        let server = MockServer::start().await;
+        for (market, code) in [("SIG-EUR", 309), ("TIME-EUR", 304), ("BAD-EUR", 205)] {
+            Mock::given(method("GET"))
+                .and(path("/v2/ticker/price"))
+                .and(query_param("market", market))
+                .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "errorCode": code,
+                    "error": "Rejected.",
+                })))
+                .mount(&server)
+                .await;
+        }
 
-        Ok(response)
-    }
+        let client = mock_client(&server);
 
-    /// Returns the withdrawal history of the account.
-    ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
-    /// use bitvavo_api as bitvavo;
-    /// use bitvavo::types::Deposit;
-    ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
-    ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let deposit_history = c.withdrawal_history(None, None, None, None).await.unwrap();
-    ///
-    /// println!("Number of withdrawals: {}", deposit_history.len());
-    /// # })
-    pub async fn withdrawal_history(
-        &self,
-        symbol: Option<&str>,
-        limit: Option<u64>,
-        start: Option<u64>,
-        end: Option<u64>,
-    ) -> Result<Vec<Withdrawal>> {
-        let mut url = String::from("withdrawalHistory");
+        let err = client.ticker_price("SIG-EUR").await.unwrap_err();
+        assert!(matches!(err, Error::SignatureRejected { code: 309, .. }));
+        assert_eq!(err.kind(), ErrorKind::Auth);
 
-        if let Some(symbol) = symbol {
-            url.push_str(&format!("?symbol={symbol}"));
-        }
-        if let Some(limit) = limit {
-            url.push_str(&format!("&limit={limit}"));
-        }
-        if let Some(start) = start {
-            url.push_str(&format!("&start={start}"));
-        }
-        if let Some(end) = end {
-            url.push_str(&format!("&end={end}"));
-        }
+        let err = client.ticker_price("TIME-EUR").await.unwrap_err();
+        assert!(matches!(err, Error::TimestampOutOfRange { code: 304, .. }));
+        assert_eq!(err.kind(), ErrorKind::Auth);
 
-        let request = self.get(url)?;
+        let err = client.ticker_price("BAD-EUR").await.unwrap_err();
+        assert!(matches!(err, Error::Bitvavo { code: 205, .. }));
+    }
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+    #[tokio::test]
+    async fn network_error_kind() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = Client::new()
+            .with_base_url(format!("http://{addr}").parse().unwrap())
+            .unwrap();
+        let err = client
+            .time()
+            .await
+            .expect_err("Getting the time should fail");
+        assert_eq!(err.kind(), ErrorKind::Network);
+    }
 
-        Ok(response)
+    #[tokio::test]
+    async fn ping() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 }))
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .mount(&server)
+            .await;
+
+        let latency = mock_client(&server)
+            .ping()
+            .await
+            .expect("Pinging should succeed");
+        assert!(latency >= Duration::from_millis(50));
     }
 
-    // Trading endpoints
+    #[test]
+    fn validate_credentials() {
+        let key = || String::from("key");
+        let secret = || "ab".repeat(64);
 
-    /// Places an order on the exchange.
-    ///
-    /// ```no_run
-    /// # tokio_test::block_on(async {
-    /// use bitvavo_api as bitvavo;
-    /// use bitvavo::types::{Order, OrderType, TradeSide, TimeInForce};
-    ///
-    /// let key = String::from("YOUR_API_KEY");
-    /// let secret = String::from("YOUR_API_SECRET");
-    ///
-    /// let c = bitvavo::Client::with_credentials(key, secret);
-    /// let response = c.place_order(Order {
-    ///     market: String::from("BTC-EUR"),
-    ///     side: TradeSide::Sell,
-    ///     order_type: OrderType::Limit,
-    ///     client_order_id: None,
-    ///     amount: Some(String::from("1")),
-    ///     amount_quote: None,
-    ///     price: Some(String::from("1000000")),
-    ///     trigger_amount: None,
-    ///     trigger_type: None,
-    ///     trigger_reference: None,
-    ///     time_in_force: Some(TimeInForce::ImmediateOrCancel),
-    ///     post_only: None,
-    ///     self_trade_prevention: None,
-    ///     disable_market_protection: false,
-    ///     response_required: false,
-    /// }).await.unwrap();
-    ///
-    /// println!("Order ID: {}", response.order_id);
-    /// # })
-    /// ```
-    pub async fn place_order(&self, order: Order) -> Result<OrderResponse> {
-        let request = self.post("order", order)?;
+        Client::try_with_credentials(key(), secret()).expect("Valid credentials should pass");
 
-        let http_response = request.send().await?;
-        let response = response_from_request(http_response).await?;
+        let err = Client::try_with_credentials(String::new(), secret()).unwrap_err();
+        assert!(matches!(err, Error::InvalidSecret(BadSecret::EmptyKey)));
 
-        Ok(response)
+        let err = Client::try_with_credentials(key(), String::new()).unwrap_err();
+        assert!(matches!(err, Error::InvalidSecret(BadSecret::EmptySecret)));
+
+        for malformed in ["not a secret", "abc"] {
+            let err = Client::try_with_credentials(key(), malformed.to_string()).unwrap_err();
+            assert!(matches!(err, Error::InvalidSecret(BadSecret::Hex(_))));
+            assert_eq!(err.kind(), ErrorKind::Config);
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn debug_redacts_credentials() {
+        let client =
+            Client::with_credentials(String::from("my-api-key"), String::from("my-api-secret"));
+
+        let debug = format!("{client:?}");
+        assert!(debug.contains(r#"Credentials { key: "***", secret: "***" }"#));
+        assert!(!debug.contains("my-api-key"));
+        assert!(!debug.contains("my-api-secret"));
+    }
 
     #[tokio::test]
-    async fn get_time() {
-        let client = Client::new();
+    async fn cloned_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 })),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let cloned = client.clone();
+
+        let handle = tokio::spawn(async move { cloned.time().await });
         client
             .time()
             .await
             .expect("Getting the time should succeed");
+        handle
+            .await
+            .unwrap()
+            .expect("Getting the time from a clone should succeed");
     }
 
     #[tokio::test]
-    async fn get_assets() {
-        let client = Client::new();
-        client
-            .assets()
+    async fn timed_uses_time_source() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ticker/24h"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "market": "BTC-EUR",
+                "timestamp": 1548172480125u64,
+            })))
+            .mount(&server)
+            .await;
+
+        let t24h = mock_client(&server)
+            .with_time_source(|| 1548172481125)
+            .ticker_24h_timed("BTC-EUR")
             .await
-            .expect("Getting the assets should succeed");
+            .expect("Getting the ticker should succeed");
+        assert_eq!(t24h.age(), Some(Duration::from_secs(1)));
     }
 
     #[tokio::test]
-    async fn get_asset() {
-        let client = Client::new();
-        client
-            .asset("BTC")
+    async fn warmup() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        mock_client(&server)
+            .warmup()
             .await
-            .expect("Getting the asset should succeed");
+            .expect("Warming up should succeed");
     }
 
     #[tokio::test]
-    async fn get_markets() {
-        let client = Client::new();
-        client
-            .markets()
+    async fn server_time_skew() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "time": 1539180275424u64 })),
+            )
+            .mount(&server)
+            .await;
+
+        // The local clock reads 1539180270000 before the request and a second later after it.
+        let time = Arc::new(AtomicU64::new(1539180270000));
+        let client = mock_client(&server).with_time_source({
+            let time = time.clone();
+            move || time.fetch_add(1000, Ordering::SeqCst)
+        });
+        let skew = client
+            .server_time_skew()
             .await
-            .expect("Getting the markets should succeed");
+            .expect("Measuring the skew should succeed");
+        assert_eq!(skew, 4924);
+
+        // A local clock ahead of the server's gives a negative skew.
+        let client = mock_client(&server).with_time_source(|| 1539180280424);
+        let skew = client
+            .server_time_skew()
+            .await
+            .expect("Measuring the skew should succeed");
+        assert_eq!(skew, -5000);
     }
 
+    #[cfg(feature = "decimal")]
     #[tokio::test]
-    async fn get_market() {
-        let client = Client::new();
-        client
-            .market("BTC-EUR")
+    async fn effective_fees() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/account/fees"))
+            .and(query_param("market", "BTC-EUR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tier": 2,
+                "volume": "250000.00",
+                "taker": "0.0020",
+                "maker": "0.0010"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/account/fees"))
+            .and(query_param("market", "ETH-EUR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tier": 2,
+                "volume": "250000.00",
+                "taker": "0.0020",
+                "maker": "n/a"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let (maker, taker) = client
+            .effective_fees("BTC-EUR")
             .await
-            .expect("Getting the market should succeed");
+            .expect("Getting the fees should succeed");
+        assert_eq!(maker, "0.001".parse::<Decimal>().unwrap());
+        assert_eq!(taker, "0.002".parse::<Decimal>().unwrap());
+
+        let err = client
+            .effective_fees("ETH-EUR")
+            .await
+            .expect_err("An unparseable rate should fail");
+        assert!(matches!(err, Error::InvalidNumber { field: "maker", .. }));
     }
 
     #[tokio::test]
-    async fn get_order_book() {
-        let client = Client::new();
-        client
-            .order_book("BTC-EUR", Some(2))
+    async fn cancel_stale_orders() {
+        let now = SystemClock.now_millis();
+
+        let order = |id: &str, created: u64| {
+            fixtures::order(serde_json::json!({
+                "orderId": id,
+                "created": created,
+                "updated": created,
+            }))
+        };
+
+        let stale = "1be6d0df-d5dc-4b53-a250-3376f3b393e6";
+        let filled = "3be6d0df-d5dc-4b53-a250-3376f3b393e6";
+        let fresh = "2be6d0df-d5dc-4b53-a250-3376f3b393e6";
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/ordersOpen"))
+            .and(query_param("market", "BTC-EUR"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                order(stale, now - 120_000),
+                order(filled, now - 90_000),
+                order(fresh, now),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/order"))
+            .and(query_param("orderId", stale))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "orderId": stale })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/order"))
+            .and(query_param("orderId", filled))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "errorCode": 240,
+                "error": "No order found.",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_client(&server)
+            .cancel_stale_orders("BTC-EUR", Duration::from_secs(60))
             .await
-            .expect("Getting the order book should succeed");
+            .expect("Listing stale orders should succeed");
+        assert_eq!(result.cancelled, [Uuid::parse_str(stale).unwrap()]);
+
+        assert_eq!(result.failed.len(), 1);
+        let (order_id, err) = &result.failed[0];
+        assert_eq!(*order_id, Uuid::parse_str(filled).unwrap());
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    /// Serve a single `/v2/time` response, after resetting the first `resets` connections.
+    fn flaky_server(resets: usize) -> Url {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for _ in 0..resets {
+                let (stream, _) = listener.accept().unwrap();
+                drop(stream);
+            }
+
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).unwrap();
+
+            let body = r#"{"time":1539180275424}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        format!("http://{addr}").parse().unwrap()
     }
 
     #[tokio::test]
-    async fn get_trades() {
-        let client = Client::new();
-        client
-            .trades("BTC-EUR", None, None, None, None, None)
+    async fn retry_get_after_connection_reset() {
+        let client = Client::new().with_base_url(flaky_server(1)).unwrap();
+        let time = client
+            .time()
             .await
-            .expect("Getting the order book should succeed");
+            .expect("Getting the time should succeed");
+        assert_eq!(time, 1539180275424);
+
+        let client = Client::new()
+            .with_base_url(flaky_server(1))
+            .unwrap()
+            .with_retry_config(RetryConfig {
+                retry_connect_errors: false,
+                ..Default::default()
+            });
+        let err = client
+            .time()
+            .await
+            .expect_err("Getting the time should fail without retries");
+        assert!(matches!(err, Error::Reqwest { .. }));
     }
 
     #[tokio::test]
-    async fn get_candles() {
+    async fn account_endpoints_need_credentials() {
+        // The request fails before it is sent, so it never reaches the API.
         let client = Client::new();
-        client
-            .candles("BTC-EUR", CandleInterval::OneDay, Some(1), None, None)
+
+        let err = client
+            .balances()
             .await
-            .expect("Getting the candles should succeed");
+            .expect_err("Getting the balances without credentials should fail");
+        assert!(matches!(err, Error::Unsupported(_)));
+        assert_eq!(err.kind(), ErrorKind::Config);
     }
 
     #[tokio::test]
-    async fn get_ticker_prices() {
-        let client = Client::new();
-        client
-            .ticker_prices()
+    async fn no_retry_for_orders() {
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url(flaky_server(1))
+            .unwrap();
+        let order = OrderBuilder::market_buy("BTC-EUR", "100").build();
+
+        let err = client
+            .place_order(order)
             .await
-            .expect("Getting the markets should succeed");
+            .expect_err("Placing the order should not be retried");
+        assert!(matches!(err, Error::Reqwest { .. }));
+    }
+
+    /// Counts the warnings logged by this crate through `tracing`.
+    #[derive(Clone, Default)]
+    struct WarningCounter(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl WarningCounter {
+        fn count(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    impl tracing::Subscriber for WarningCounter {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let metadata = event.metadata();
+            if *metadata.level() == tracing::Level::WARN
+                && metadata.target().starts_with("bitvavo_api")
+            {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
     }
 
     #[tokio::test]
-    async fn get_ticker_price() {
-        let client = Client::new();
+    async fn rate_limit_warning() {
+        let server = MockServer::start().await;
+        for (endpoint, remaining) in [("/v2/time", "50"), ("/v2/ping", "49")] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header(RATE_LIMIT_REMAINING, remaining)
+                        .set_body_json(serde_json::json!({ "time": 1 })),
+                )
+                .mount(&server)
+                .await;
+        }
+
+        let warnings = WarningCounter::default();
+        let _guard = tracing::subscriber::set_default(warnings.clone());
+
+        let client = mock_client(&server);
+        assert_eq!(client.rate_limit_remaining(), None);
+
         client
-            .ticker_price("BTC-EUR")
+            .time()
             .await
-            .expect("Getting the market should succeed");
+            .expect("Getting the time should succeed");
+        assert_eq!(client.rate_limit_remaining(), Some(50));
+        assert_eq!(warnings.count(), 0);
+
+        client
+            .get_with_raw::<serde_json::Value>("ping")
+            .await
+            .expect("Getting the endpoint should succeed");
+        assert_eq!(client.rate_limit_remaining(), Some(49));
+        assert_eq!(warnings.count(), 1);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn non_finite_numbers_warning() {
+        let balances: Vec<Balance> = serde_json::from_str(
+            r#"[
+                {"symbol": "BTC", "available": "NaN", "inOrder": "0"},
+                {"symbol": "ETH", "available": "1", "inOrder": ""},
+                {"symbol": "EUR", "available": "100", "inOrder": "50"}
+            ]"#,
+        )
+        .expect("Deserializing the balances should succeed");
+
+        let warnings = WarningCounter::default();
+        let _guard = tracing::subscriber::set_default(warnings.clone());
+
+        assert_eq!(balances[2].total(), Some(Decimal::from(150)));
+        assert_eq!(warnings.count(), 0);
+
+        assert_eq!(balances[0].total(), None);
+        assert_eq!(balances[1].total(), None);
+        assert_eq!(warnings.count(), 2);
     }
 
     #[tokio::test]
-    async fn get_ticker_books() {
-        let client = Client::new();
+    async fn limits_are_clamped() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/trades"))
+            .and(query_param("limit", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/BTC-EUR/candles"))
+            .and(query_param("limit", "1440"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&server)
+            .await;
+
+        let warnings = WarningCounter::default();
+        let _guard = tracing::subscriber::set_default(warnings.clone());
+
+        let client = mock_client(&server);
+
         client
-            .ticker_books()
+            .trades("BTC-EUR", Some(1000), None, None, None, None)
             .await
-            .expect("Getting the ticker books should succeed");
+            .expect("Getting the trades should succeed");
+        assert_eq!(warnings.count(), 0);
+
+        client
+            .trades("BTC-EUR", Some(5000), None, None, None, None)
+            .await
+            .expect("Getting the trades should succeed");
+        assert_eq!(warnings.count(), 1);
+
+        client
+            .candles("BTC-EUR", CandleInterval::OneMinute, Some(2000), None, None)
+            .await
+            .expect("Getting the candles should succeed");
+        assert_eq!(warnings.count(), 2);
     }
 
     #[tokio::test]
-    async fn get_ticker_book() {
-        let client = Client::new();
-        client
-            .ticker_book("BTC-EUR")
+    async fn response_too_large() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/time"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"time":1}"#))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/markets"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(" ".repeat(1 << 20)))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server).with_max_response_size(10);
+
+        let time = client
+            .time()
             .await
-            .expect("Getting the ticker book should succeed");
+            .expect("Getting the time should succeed");
+        assert_eq!(time, 1);
+
+        let err = client
+            .markets()
+            .await
+            .expect_err("Getting an oversized response should fail");
+        assert!(matches!(
+            err,
+            Error::ResponseTooLarge { ref endpoint, limit: 10 } if endpoint == "/v2/markets"
+        ));
+        assert_eq!(err.kind(), ErrorKind::Decode);
+
+        let err = client
+            .get_with_raw::<serde_json::Value>("markets?market=BTC-EUR")
+            .await
+            .expect_err("Getting an oversized response should fail");
+        assert!(matches!(
+            err,
+            Error::ResponseTooLarge { ref endpoint, .. } if endpoint == "/v2/markets?market=BTC-EUR"
+        ));
     }
 
     #[tokio::test]
-    async fn get_tickers_24h() {
-        let client = Client::new();
+    async fn empty_body_on_success() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/order"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/order"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
         client
-            .tickers_24h()
+            .cancel_order("BTC-EUR", Uuid::nil())
             .await
-            .expect("Getting the 24h tickers should succeed");
+            .expect("Cancelling the order should succeed");
+
+        let request = client
+            .signed_get(client.url(&["order"], &[]))
+            .expect("Building the request should succeed");
+        let http_response = request.send().await.expect("Sending should succeed");
+        let response: Option<OrderStatus> = response_from_request(http_response)
+            .await
+            .expect("Decoding an empty body should succeed");
+        assert!(response.is_none());
     }
 
     #[tokio::test]
-    async fn get_ticker_24h() {
-        let client = Client::new();
+    async fn redirects_are_not_followed() {
+        let elsewhere = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(0)
+            .mount(&elsewhere)
+            .await;
+
+        let server = MockServer::start().await;
+        let location = format!("{}/status", elsewhere.uri());
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", location.as_str()))
+            .mount(&server)
+            .await;
+
+        let err = mock_client(&server)
+            .balances()
+            .await
+            .expect_err("A redirect should fail the request");
+        assert!(matches!(
+            err,
+            Error::UnexpectedRedirect { location: Some(ref l) } if *l == location
+        ));
+    }
+
+    #[tokio::test]
+    async fn order_refs() {
+        let id = Uuid::from_u128(1);
+        let client_id = Uuid::from_u128(2);
+
+        let server = MockServer::start().await;
+        for (param, value) in [("orderId", id), ("clientOrderId", client_id)] {
+            Mock::given(method("GET"))
+                .and(path("/v2/order"))
+                .and(query_param("market", "BTC-EUR"))
+                .and(query_param(param, value.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(fixtures::order(
+                    serde_json::json!({ "orderId": id, "clientOrderId": client_id }),
+                )))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("DELETE"))
+                .and(path("/v2/order"))
+                .and(query_param("market", "BTC-EUR"))
+                .and(query_param(param, value.to_string()))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = mock_client(&server);
+
+        let order = client
+            .get_order("BTC-EUR", id)
+            .await
+            .expect("Getting the order by id should succeed");
+        assert_eq!(order.order_id, id);
+        let order = client
+            .get_order("BTC-EUR", OrderRef::ByClientId(client_id))
+            .await
+            .expect("Getting the order by client id should succeed");
+        assert_eq!(order.client_order_id, Some(client_id));
+
         client
-            .ticker_24h("BTC-EUR")
+            .cancel_order("BTC-EUR", id)
             .await
-            .expect("Getting the 24h tickers should succeed");
+            .expect("Cancelling the order by id should succeed");
+        client
+            .cancel_order("BTC-EUR", OrderRef::ByClientId(client_id))
+            .await
+            .expect("Cancelling the order by client id should succeed");
+    }
+
+    #[tokio::test]
+    async fn wait_for_fill() {
+        let order = |status: &str| fixtures::order(serde_json::json!({ "status": status }));
+        let id = Uuid::parse_str("1be6d0df-d5dc-4b53-a250-3376f3b393e6").unwrap();
+        let poll = Duration::from_millis(10);
+
+        // Filled after a few polls.
+        let server = MockServer::start().await;
+        for status in ["new", "partiallyFilled"] {
+            Mock::given(method("GET"))
+                .and(path("/v2/order"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(order(status)))
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/v2/order"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(order("filled")))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let status = mock_client(&server)
+            .wait_for_fill("BTC-EUR", id, poll, Duration::from_secs(5))
+            .await
+            .expect("Waiting for the order should succeed");
+        assert_eq!(status.status, OrderState::Filled);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        // Cancelled once the timeout passes.
+        let server = MockServer::start().await;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        Mock::given(method("GET"))
+            .and(path("/v2/order"))
+            .respond_with({
+                let cancelled = cancelled.clone();
+                move |_: &wiremock::Request| {
+                    let status = if cancelled.load(Ordering::SeqCst) {
+                        "canceled"
+                    } else {
+                        "new"
+                    };
+                    ResponseTemplate::new(200).set_body_json(order(status))
+                }
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/order"))
+            .and(query_param("orderId", id.to_string()))
+            .respond_with({
+                let cancelled = cancelled.clone();
+                move |_: &wiremock::Request| {
+                    cancelled.store(true, Ordering::SeqCst);
+                    ResponseTemplate::new(200)
+                }
+            })
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let status = mock_client(&server)
+            .wait_for_fill("BTC-EUR", id, poll, Duration::from_millis(50))
+            .await
+            .expect("Waiting for the order should succeed");
+        assert_eq!(status.status, OrderState::Canceled);
     }
 
     #[tokio::test]
@@ -1132,5 +5575,17 @@ mod tests {
 
             Ok(())
         }
+
+        #[tokio::test]
+        async fn get_my_trades() -> Result<()> {
+            let client = Client::with_credentials(API_KEY.to_string(), API_SECRET.to_string());
+
+            client
+                .my_trades("BTC-EUR", None, None, None, None, None)
+                .await
+                .expect("Getting the trades of the account should succeed");
+
+            Ok(())
+        }
     }
 }
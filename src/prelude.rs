@@ -0,0 +1,31 @@
+//! The most commonly used items, for glob importing.
+//!
+//! This includes the [`Client`] with its [`Error`] and [`Result`] types, and the types used as
+//! arguments to its methods. The client is only included with the `client` feature:
+//!
+//! ```
+//! use bitvavo_api::prelude::*;
+//!
+//! # #[cfg(feature = "client")]
+//! let c = Client::new();
+//! let order = OrderBuilder::new("BTC-EUR", TradeSide::Buy, OrderType::Limit)
+//!     .amount("0.1")
+//!     .price("30000")
+//!     .time_in_force(TimeInForce::GoodTillCancelled)
+//!     .build();
+//! ```
+
+pub use crate::types::{
+    BookSide, CandleInterval, Depth, MarketPair, MarketStatus, Order, OrderAmount, OrderBuilder,
+    OrderRef, OrderType, SelfTradePrevention, Symbol, TimeInForce, TradeSide,
+    TransactionHistoryOptions, TransactionKind, Trigger, TriggerReference, TriggerType, TypedOrder,
+    WithdrawOrder,
+};
+#[cfg(feature = "client")]
+pub use crate::{Client, RetryConfig};
+pub use crate::{Error, Result};
+
+#[cfg(feature = "decimal")]
+pub use crate::types::{Rounding, RoundingMode};
+#[cfg(feature = "decimal")]
+pub use crate::Decimal;
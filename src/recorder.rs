@@ -0,0 +1,128 @@
+//! Recording the balances of an account over time.
+//!
+//! Bitvavo does not offer a history of balances, only the current ones. A [`BalanceRecorder`]
+//! builds such a history locally, from samples of the current balances taken at a rate of the
+//! caller's choosing.
+
+use std::collections::VecDeque;
+
+use crate::types::Balance;
+use crate::{Client, Result};
+
+/// The balances of an account at a point in time.
+#[derive(Debug)]
+pub struct BalanceSnapshot {
+    /// The time the balances were sampled at, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub balances: Vec<Balance>,
+}
+
+/// Keeps the most recent snapshots of the balances of an account in memory.
+///
+/// Once the recorder holds `capacity` snapshots, the oldest is dropped for each new one.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::recorder::BalanceRecorder;
+///
+/// let key = String::from("YOUR_API_KEY");
+/// let secret = String::from("YOUR_API_SECRET");
+///
+/// let c = bitvavo::Client::with_credentials(key, secret);
+/// // A day of snapshots, when sampling every minute.
+/// let mut recorder = BalanceRecorder::new(24 * 60);
+///
+/// let snapshot = recorder.sample(&c).await.unwrap();
+/// println!("Holding {} assets", snapshot.balances.len());
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct BalanceRecorder {
+    capacity: usize,
+    snapshots: VecDeque<BalanceSnapshot>,
+}
+
+impl BalanceRecorder {
+    /// Create a recorder keeping at most `capacity` snapshots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Sample the current balances of the account, and record them.
+    pub async fn sample(&mut self, client: &Client) -> Result<&BalanceSnapshot> {
+        let balances = client.balances().await?;
+        let timestamp = client.now_millis();
+
+        self.push(BalanceSnapshot {
+            timestamp,
+            balances,
+        });
+
+        Ok(self.snapshots.back().expect("A snapshot was just recorded"))
+    }
+
+    /// Record a snapshot, dropping the oldest one if the recorder is full.
+    pub fn push(&mut self, snapshot: BalanceSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Iterate over the recorded snapshots, from oldest to newest.
+    pub fn snapshots(&self) -> impl Iterator<Item = &BalanceSnapshot> {
+        self.snapshots.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn keeps_most_recent_snapshots() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"symbol": "BTC", "available": "1.5", "inOrder": "0.5"},
+            ])))
+            .mount(&server)
+            .await;
+
+        let time = Arc::new(AtomicU64::new(0));
+        let client = Client::with_credentials("key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap()
+            .with_time_source({
+                let time = time.clone();
+                move || time.fetch_add(1000, Ordering::SeqCst)
+            });
+
+        let mut recorder = BalanceRecorder::new(2);
+        for _ in 0..3 {
+            recorder
+                .sample(&client)
+                .await
+                .expect("Sampling the balances should succeed");
+        }
+
+        let timestamps: Vec<_> = recorder.snapshots().map(|s| s.timestamp).collect();
+        // Signing each request reads the time as well, before the snapshot is taken.
+        assert_eq!(timestamps, [3000, 5000]);
+        assert!(recorder.snapshots().all(|s| s.balances.len() == 1));
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+
+use hmac::digest::{InvalidLength, KeyInit};
+use hmac::Mac;
+use zeroize::Zeroizing;
+
+use crate::Result;
+
+/// A scheme for signing requests to the API.
+pub trait Signer {
+    /// Sign a request, returning the value of the `Bitvavo-Access-Signature` header.
+    ///
+    /// The `slug` is the path of the request including its query, e.g. `/v2/order`, and `body`
+    /// is empty for requests without one.
+    fn sign(&self, timestamp: &str, method: &str, slug: &str, body: &str) -> Result<String>;
+}
+
+/// Signs requests using a MAC keyed with the API secret, hex encoding the result.
+///
+/// The MAC is keyed with the bytes of the secret string as is. Bitvavo hands out secrets as hex
+/// strings, but they are not hex decoded, matching Bitvavo's reference clients.
+///
+/// The MAC is keyed once, when the signer is created, and cloned for each request. The secret
+/// itself is zeroized as soon as the MAC is keyed, and not kept.
+///
+/// The keyed MAC, and the clones made for each request, are not zeroized when dropped. Keying
+/// HMAC leaves the inner and outer hash states derived from the secret in memory, and the `hmac`
+/// and `digest` crates do not implement [`Zeroize`] for them, nor expose them to be overwritten
+/// safely. These states are not the secret, and recovering it from them means inverting the
+/// hash, but they are enough to sign requests for as long as they linger in memory.
+///
+/// [`Zeroize`]: zeroize::Zeroize
+///
+/// Bitvavo uses HMAC-SHA256, available as [`HmacSha256Signer`].
+pub struct MacSigner<M> {
+    mac: Result<M, InvalidLength>,
+}
+
+/// Signs requests using HMAC-SHA256 keyed with the API secret.
+///
+/// This is the scheme Bitvavo uses, and the one used by [`Client::with_credentials`].
+///
+/// [`Client::with_credentials`]: crate::Client::with_credentials
+pub type HmacSha256Signer = MacSigner<hmac::Hmac<sha2::Sha256>>;
+
+impl<M: KeyInit> MacSigner<M> {
+    /// Create a new signer using the given API secret.
+    ///
+    /// A secret of a length the MAC does not accept is reported when signing.
+    pub fn new(secret: String) -> Self {
+        let secret = Zeroizing::new(secret);
+        Self {
+            mac: <M as KeyInit>::new_from_slice(secret.as_bytes()),
+        }
+    }
+}
+
+/// The secret is redacted, so it never ends up in logs.
+impl<M> fmt::Debug for MacSigner<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MacSigner").field("secret", &"***").finish()
+    }
+}
+
+impl<M: Mac + Clone> Signer for MacSigner<M> {
+    fn sign(&self, timestamp: &str, method: &str, slug: &str, body: &str) -> Result<String> {
+        let mac = self.mac.clone()?;
+
+        let mac = mac
+            .chain_update(timestamp)
+            .chain_update(method)
+            .chain_update(slug)
+            .chain_update(body);
+
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from Bitvavo's API documentation, signed with the secret `bitvavo`.
+    #[test]
+    fn hmac_sha256_signature() {
+        let signer = HmacSha256Signer::new(String::from("bitvavo"));
+
+        let signature = signer
+            .sign(
+                "1548172481125",
+                "POST",
+                "/v2/order",
+                r#"{"market":"BTC-EUR","side":"buy","price":"5000","amount":"1.23","orderType":"limit"}"#,
+            )
+            .expect("Signing should succeed");
+
+        assert_eq!(
+            signature,
+            "44d022723a20973a18f7ee97398b9fdd405d2d019c8d39e24b8cc0dcb39ca016"
+        );
+    }
+
+    #[test]
+    fn hex_secret_is_not_decoded() {
+        let secret = "ab".repeat(64);
+        let signer = HmacSha256Signer::new(secret.clone());
+
+        let signature = signer
+            .sign("1548172481125", "GET", "/v2/account", "")
+            .expect("Signing should succeed");
+
+        let key = secret.as_bytes();
+        let mut mac = <hmac::Hmac<sha2::Sha256> as KeyInit>::new_from_slice(key).unwrap();
+        mac.update(b"1548172481125GET/v2/account");
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+    }
+
+    #[test]
+    fn signer_is_reused() {
+        let signer = HmacSha256Signer::new(String::from("bitvavo"));
+
+        let first = signer.sign("1548172481125", "GET", "/v2/account", "");
+        let second = signer.sign("1548172481125", "GET", "/v2/account", "");
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn other_mac_signature() {
+        let signer = MacSigner::<hmac::Hmac<sha2::Sha512>>::new(String::from("bitvavo"));
+
+        let signature = signer
+            .sign(
+                "1548172481125",
+                "POST",
+                "/v2/order",
+                r#"{"market":"BTC-EUR","side":"buy","price":"5000","amount":"1.23","orderType":"limit"}"#,
+            )
+            .expect("Signing should succeed");
+
+        assert_eq!(
+            signature,
+            "1129a2b43d3842ab9cafe7d18fbf77d41c70a7906dd92ba0a17c25a302defe9e\
+             b2ed3f66a8faf10a02b6ba081da97d75c5145b6e82048ed0b99652d59c43cfbf"
+        );
+    }
+}
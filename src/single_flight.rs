@@ -0,0 +1,131 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::watch;
+
+use crate::{Error, Result};
+
+/// The result of a request, once it has completed, as shared with the callers waiting on it.
+type Outcome<T> = Option<Result<T, Arc<Error>>>;
+
+/// Coalesces concurrent calls fetching the same data, so a burst of callers shares a single
+/// request.
+///
+/// The first caller makes the request, and callers that arrive while it is in flight wait for
+/// it and get a copy of its result, whether a success or an error. An error shared with waiting
+/// callers is returned to all of them, the first included, as [`Error::Shared`]. If the first
+/// caller is dropped before the request completes, a waiting caller makes a request of its own.
+/// Callers arriving after a request completed make a new one, so results are never served
+/// stale.
+#[derive(Debug)]
+pub(crate) struct SingleFlight<T> {
+    /// The request in flight, if any. The lock is only held to look at or replace it, never
+    /// across the request itself.
+    in_flight: Mutex<Option<watch::Receiver<Outcome<T>>>>,
+}
+
+impl<T> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self {
+            in_flight: Mutex::new(None),
+        }
+    }
+}
+
+/// Clears the request in flight when the caller making it is done with it, or is dropped.
+struct Flight<'a, T> {
+    single_flight: &'a SingleFlight<T>,
+    done: watch::Sender<Outcome<T>>,
+}
+
+impl<T> Flight<'_, T> {
+    /// Stop sharing the request, returning whether any callers are still waiting on it.
+    fn finish(&self) -> bool {
+        let mut in_flight = self.single_flight.in_flight.lock().unwrap();
+        if let Some(receiver) = &*in_flight {
+            if receiver.same_channel(&self.done.subscribe()) {
+                *in_flight = None;
+            }
+        }
+        self.done.receiver_count() > 0
+    }
+}
+
+impl<T> Drop for Flight<'_, T> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// Run `fetch`, unless a request is already in flight, in which case its result is returned
+    /// instead.
+    pub(crate) async fn run<F, Fut>(&self, fetch: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        loop {
+            // Either wait on the request in flight, or become the caller making it.
+            let waiting = {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                match &*in_flight {
+                    Some(receiver) => Ok(receiver.clone()),
+                    None => {
+                        let (done, receiver) = watch::channel(None);
+                        *in_flight = Some(receiver);
+                        Err(Flight {
+                            single_flight: self,
+                            done,
+                        })
+                    }
+                }
+            };
+
+            let mut receiver = match waiting {
+                Ok(receiver) => receiver,
+                Err(flight) => return flight.run(fetch).await,
+            };
+
+            let outcome = match receiver.wait_for(Option::is_some).await {
+                Ok(outcome) => outcome.clone(),
+                // The caller making the request was dropped, so make another.
+                Err(_) => continue,
+            };
+
+            return match outcome {
+                Some(Ok(value)) => Ok(value),
+                Some(Err(err)) => Err(Error::Shared(err)),
+                None => unreachable!("Waited for the request to complete"),
+            };
+        }
+    }
+}
+
+impl<T: Clone> Flight<'_, T> {
+    async fn run<F, Fut>(self, fetch: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let result = fetch().await;
+
+        // Nobody can start waiting once the request is no longer in flight, so an error only
+        // needs to be shared if somebody already is.
+        if !self.finish() {
+            return result;
+        }
+
+        match result {
+            Ok(value) => {
+                self.done.send_replace(Some(Ok(value.clone())));
+                Ok(value)
+            }
+            Err(err) => {
+                let err = Arc::new(err);
+                self.done.send_replace(Some(Err(err.clone())));
+                Err(Error::Shared(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//! Canned responses for testing code that uses the [`Client`] without network access.
+//!
+//! [`Client::mock`] returns a client whose requests are served by a [`MockTransport`] with a
+//! fixed set of fixtures, one for each endpoint:
+//!
+//! | Endpoint                          | Fixture                                        |
+//! |-----------------------------------|------------------------------------------------|
+//! | `time`                            | time `1539180275424`                           |
+//! | `assets`, `assets?symbol`         | the `BTC` asset                                |
+//! | `markets`, `markets?market`       | the trading `BTC-EUR` market                   |
+//! | `{market}/book`                   | a book with two bids and two asks, nonce `1`   |
+//! | `{market}/trades`                 | one buy and one sell trade                     |
+//! | `{market}/candles`                | one candle                                     |
+//! | `ticker/price`, `?market`         | `BTC-EUR` at `30000`                           |
+//! | `ticker/book`, `?market`          | `BTC-EUR` with a bid of `29990`, ask `30010`   |
+//! | `ticker/24h`, `?market`           | `BTC-EUR` opening at `29000`, last `30000`     |
+//! | `account`                         | taker fee `0.0025`, maker fee `0.0015`         |
+//! | `account/fees`                    | tier `0`, same fees as the account             |
+//! | `balance`, `balance?symbol`       | `1.5` available and `0.5` in order of `BTC`    |
+//! | `deposit`                         | a `BTC` deposit address                        |
+//! | `depositHistory`                  | one completed `BTC` deposit                    |
+//! | `withdrawal` (POST)               | a successful withdrawal                        |
+//! | `withdrawalHistory`               | one completed `BTC` withdrawal                 |
+//! | `order` (POST), `order?orderId`   | a new limit buy order on `BTC-EUR`             |
+//! | `order?orderId` (DELETE)          | the cancelled order's id                       |
+//! | `ordersOpen`                      | the same order                                 |
+//! | `trades?market`                   | one settled fill on `BTC-EUR`                  |
+//!
+//! Any other request is answered with a `501` carrying an error with the code
+//! [`NO_RESPONSE_CODE`], which names the request. Individual responses can be overridden using
+//! [`MockTransport::with_response`]:
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use bitvavo_api as bitvavo;
+//! use bitvavo::testing::MockTransport;
+//!
+//! let transport = MockTransport::with_fixtures().with_response(
+//!     "GET",
+//!     "ticker/price?market",
+//!     200,
+//!     r#"{"market":"BTC-EUR","price":"42"}"#,
+//! );
+//!
+//! let c = bitvavo::Client::new().with_transport(transport);
+//! let ticker = c.ticker_price("BTC-EUR").await.unwrap();
+//!
+//! assert_eq!(ticker.price.as_deref(), Some("42"));
+//! # })
+//! ```
+//!
+//! To test against real responses instead, they can be recorded once and replayed afterwards
+//! using a [`CassetteTransport`].
+//!
+//! [`Client`]: crate::Client
+//! [`Client::mock`]: crate::Client::mock
+
+use futures_util::future::BoxFuture;
+
+use crate::transport::Transport;
+
+mod cassette;
+
+pub use cassette::CassetteTransport;
+
+/// The error code a [`MockTransport`] or [`CassetteTransport`] answers a request with when it has
+/// no response for it, as that of [`Error::Bitvavo`](crate::Error::Bitvavo).
+///
+/// Bitvavo does not use this code, so a missing response is never mistaken for an error of the
+/// API.
+pub const NO_RESPONSE_CODE: u64 = 0;
+
+/// The response to a request without a canned or recorded one, naming the request.
+fn no_response(method: &reqwest::Method, path: &str) -> http::Response<String> {
+    let body = serde_json::json!({
+        "errorCode": NO_RESPONSE_CODE,
+        "error": format!("No response for {method} {path}"),
+    });
+
+    http::Response::builder()
+        .status(http::StatusCode::NOT_IMPLEMENTED)
+        .header("content-type", "application/json")
+        .body(body.to_string())
+        .expect("A response for a missing one should be valid")
+}
+
+/// A [`Transport`] serving canned responses instead of sending requests.
+///
+/// Responses are matched on the method and the path of a request relative to `/v2/`. A path may
+/// contain `*` as a segment to match any segment, e.g. `*/book`, and end in `?name` to only match
+/// requests with a `name` query parameter. Responses added later take precedence, and ones
+/// requiring a query parameter take precedence over ones that do not.
+///
+/// [`MockTransport::with_fixtures`] serves the fixtures listed in the
+/// [module documentation](self), and so do [`MockTransport::new`] and the [`Default`]
+/// transport. [`MockTransport::empty`] starts out without any responses.
+pub struct MockTransport {
+    responses: Vec<MockResponse>,
+}
+
+struct MockResponse {
+    method: String,
+    segments: Vec<String>,
+    param: Option<String>,
+    status: u16,
+    body: String,
+}
+
+impl MockResponse {
+    fn matches(&self, request: &reqwest::Request) -> bool {
+        if !request.method().as_str().eq_ignore_ascii_case(&self.method) {
+            return false;
+        }
+
+        let segments: Vec<_> = match request.url().path_segments() {
+            Some(segments) => segments.skip(1).collect(),
+            None => return false,
+        };
+
+        if segments.len() != self.segments.len() {
+            return false;
+        }
+
+        let segments_match = self
+            .segments
+            .iter()
+            .zip(segments)
+            .all(|(expected, actual)| expected == "*" || expected == actual);
+
+        let param_matches = match &self.param {
+            Some(param) => request.url().query_pairs().any(|(name, _)| name == *param),
+            None => true,
+        };
+
+        segments_match && param_matches
+    }
+}
+
+impl MockTransport {
+    /// Create a transport serving the fixtures, the same as [`MockTransport::with_fixtures`].
+    pub fn new() -> Self {
+        Self::with_fixtures()
+    }
+
+    /// Create a transport serving the fixtures listed in the [module documentation](self).
+    pub fn with_fixtures() -> Self {
+        FIXTURES
+            .iter()
+            .fold(Self::empty(), |transport, (method, endpoint, body)| {
+                transport.with_response(method, endpoint, 200, *body)
+            })
+    }
+
+    /// Create a transport without any responses.
+    pub fn empty() -> Self {
+        Self {
+            responses: Vec::new(),
+        }
+    }
+
+    /// Serve `body` with the given `status` for requests with `method` to `endpoint`.
+    pub fn with_response(
+        mut self,
+        method: &str,
+        endpoint: &str,
+        status: u16,
+        body: impl Into<String>,
+    ) -> Self {
+        let (path, param) = match endpoint.split_once('?') {
+            Some((path, param)) => (path, Some(param.to_string())),
+            None => (endpoint, None),
+        };
+
+        self.responses.push(MockResponse {
+            method: method.to_string(),
+            segments: path.split('/').map(String::from).collect(),
+            param,
+            status,
+            body: body.into(),
+        });
+
+        self
+    }
+
+    fn respond(&self, request: &reqwest::Request) -> http::Response<String> {
+        let matching = self
+            .responses
+            .iter()
+            .rev()
+            .filter(|response| response.matches(request));
+
+        let response = matching
+            .clone()
+            .find(|response| response.param.is_some())
+            .or_else(|| matching.clone().next());
+
+        match response {
+            Some(response) => http::Response::builder()
+                .status(response.status)
+                .header("content-type", "application/json")
+                .body(response.body.clone())
+                .expect("A mock response should be valid"),
+            None => no_response(request.method(), request.url().path()),
+        }
+    }
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, reqwest::Result<reqwest::Response>> {
+        let response = self.respond(&request);
+
+        Box::pin(async move { Ok(reqwest::Response::from(response)) })
+    }
+}
+
+const FIXTURES: &[(&str, &str, &str)] = &[
+    ("GET", "time", r#"{"time": 1539180275424}"#),
+    (
+        "GET",
+        "assets",
+        concat!("[", include_str!("testing/asset.json"), "]"),
+    ),
+    ("GET", "assets?symbol", include_str!("testing/asset.json")),
+    (
+        "GET",
+        "markets",
+        concat!("[", include_str!("testing/market.json"), "]"),
+    ),
+    ("GET", "markets?market", include_str!("testing/market.json")),
+    (
+        "GET",
+        "*/book",
+        r#"{
+            "market": "BTC-EUR",
+            "nonce": 1,
+            "bids": [["29990", "0.5"], ["29980", "1"]],
+            "asks": [["30010", "0.25"], ["30020", "2"]]
+        }"#,
+    ),
+    (
+        "GET",
+        "*/trades",
+        r#"[
+            {
+                "id": "57b1159b-6bf5-4cde-9e2c-6bd6a5678baf",
+                "timestamp": 1539180275424,
+                "amount": "0.1",
+                "price": "30000",
+                "side": "buy"
+            },
+            {
+                "id": "1a2f6fa4-2d2b-4d63-8b55-5b0e6b0a6a58",
+                "timestamp": 1539180274424,
+                "amount": "0.2",
+                "price": "29995",
+                "side": "sell"
+            }
+        ]"#,
+    ),
+    (
+        "GET",
+        "*/candles",
+        r#"[[1539180000000, "29000", "30500", "28500", "30000", "120.5"]]"#,
+    ),
+    (
+        "GET",
+        "ticker/price",
+        concat!("[", include_str!("testing/ticker_price.json"), "]"),
+    ),
+    (
+        "GET",
+        "ticker/price?market",
+        include_str!("testing/ticker_price.json"),
+    ),
+    (
+        "GET",
+        "ticker/book",
+        concat!("[", include_str!("testing/ticker_book.json"), "]"),
+    ),
+    (
+        "GET",
+        "ticker/book?market",
+        include_str!("testing/ticker_book.json"),
+    ),
+    (
+        "GET",
+        "ticker/24h",
+        concat!("[", include_str!("testing/ticker_24h.json"), "]"),
+    ),
+    (
+        "GET",
+        "ticker/24h?market",
+        include_str!("testing/ticker_24h.json"),
+    ),
+    (
+        "GET",
+        "account",
+        r#"{"fees": {"taker": "0.0025", "maker": "0.0015", "volume": "10000.00"}}"#,
+    ),
+    (
+        "GET",
+        "account/fees",
+        r#"{"tier": 0, "volume": "10000.00", "taker": "0.0025", "maker": "0.0015"}"#,
+    ),
+    ("GET", "balance", include_str!("testing/balance.json")),
+    (
+        "GET",
+        "balance?symbol",
+        include_str!("testing/balance.json"),
+    ),
+    (
+        "GET",
+        "deposit",
+        r#"{"address": "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", "paymentId": null}"#,
+    ),
+    (
+        "GET",
+        "depositHistory",
+        r#"[
+            {
+                "timestamp": 1539180275424,
+                "symbol": "BTC",
+                "amount": "0.5",
+                "fee": "0",
+                "status": "completed",
+                "txId": "0c6b6a5e2b9a1e5c6c59d3b4d8e16e2b6c4a1e8e6d3c2b1a0f9e8d7c6b5a4f3e",
+                "address": "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"
+            }
+        ]"#,
+    ),
+    (
+        "POST",
+        "withdrawal",
+        r#"{"success": true, "symbol": "BTC", "amount": "0.1"}"#,
+    ),
+    (
+        "GET",
+        "withdrawalHistory",
+        r#"[
+            {
+                "timestamp": 1539180275424,
+                "symbol": "BTC",
+                "amount": "0.1",
+                "address": "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+                "txId": "8e1d3b2a4c6f5e7d9c0b1a2f3e4d5c6b7a8f9e0d1c2b3a4f5e6d7c8b9a0f1e2d",
+                "fee": "0.0001",
+                "status": "completed"
+            }
+        ]"#,
+    ),
+    ("POST", "order", include_str!("testing/order.json")),
+    ("GET", "order?orderId", include_str!("testing/order.json")),
+    (
+        "DELETE",
+        "order?orderId",
+        r#"{"orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6"}"#,
+    ),
+    (
+        "GET",
+        "ordersOpen",
+        concat!("[", include_str!("testing/order.json"), "]"),
+    ),
+    (
+        "GET",
+        "trades?market",
+        r#"[
+            {
+                "id": "108c3633-0276-4480-a902-17a01829deae",
+                "orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "timestamp": 1539180275424,
+                "market": "BTC-EUR",
+                "side": "buy",
+                "amount": "0.1",
+                "price": "29000",
+                "taker": false,
+                "fee": "4.35",
+                "feeCurrency": "EUR",
+                "settled": true
+            }
+        ]"#,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Client;
+
+    #[tokio::test]
+    async fn mock_serves_fixtures() {
+        let client = Client::mock();
+
+        assert_eq!(client.time().await.unwrap(), 1539180275424);
+        assert_eq!(client.assets().await.unwrap().len(), 1);
+        assert_eq!(client.asset("BTC").await.unwrap().symbol, "BTC");
+        assert_eq!(client.markets().await.unwrap().len(), 1);
+        assert_eq!(client.market("BTC-EUR").await.unwrap().pair, "BTC-EUR");
+        assert_eq!(client.order_book("BTC-EUR", None).await.unwrap().nonce, 1);
+        assert_eq!(
+            client
+                .trades("BTC-EUR", None, None, None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(client.ticker_prices().await.unwrap().len(), 1);
+        assert_eq!(
+            client.ticker_24h("BTC-EUR").await.unwrap().last.as_deref(),
+            Some("30000")
+        );
+        assert_eq!(client.balance("BTC").await.unwrap().available, "1.5");
+        assert_eq!(client.fees(None).await.unwrap().tier, 0);
+        assert_eq!(client.orders_open(None).await.unwrap().len(), 1);
+        assert_eq!(
+            client
+                .my_trades("BTC-EUR", None, None, None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_responses_can_be_overridden() {
+        let transport = MockTransport::with_fixtures()
+            .with_response("GET", "time", 200, r#"{"time": 42}"#)
+            .with_response(
+                "GET",
+                "ticker/price?market",
+                404,
+                r#"{"errorCode": 205, "error": "market parameter is invalid."}"#,
+            );
+        let client = Client::new().with_transport(transport);
+
+        assert_eq!(client.time().await.unwrap(), 42);
+
+        let err = client
+            .ticker_price("BAD-MARKET")
+            .await
+            .expect_err("The overridden error should be returned");
+        assert!(matches!(err, crate::Error::Bitvavo { code: 205, .. }));
+
+        // Responses that are not overridden still use the fixtures.
+        assert_eq!(client.ticker_prices().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mock_without_response() {
+        let client = Client::new().with_transport(MockTransport::empty());
+
+        let err = client
+            .time()
+            .await
+            .expect_err("A request without a response should fail");
+        assert!(matches!(
+            err,
+            crate::Error::Bitvavo { code: NO_RESPONSE_CODE, ref message }
+                if message == "No response for GET /v2/time"
+        ));
+    }
+}
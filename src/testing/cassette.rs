@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::transport::Transport;
+
+/// The request headers whose values are replaced by `***` when recording.
+const REDACTED_HEADERS: &[&str] = &[
+    "bitvavo-access-key",
+    "bitvavo-access-signature",
+    "bitvavo-access-timestamp",
+];
+
+/// Redacts a request body, as set with [`CassetteTransport::with_body_redaction`].
+type Redact = dyn Fn(&str) -> String + Send + Sync;
+
+/// A [`Transport`] that records requests and their responses to a cassette file, or replays
+/// them from one without network access.
+///
+/// A cassette is a file with one interaction per line, each a JSON object:
+///
+/// ```json
+/// {"method":"GET","path":"/v2/time","headers":{},"body":"","status":200,"response_headers":{"content-type":"application/json"},"response":"{\"time\":1539180275424}"}
+/// ```
+///
+/// The `path` includes the query, but not the host, so a cassette can be replayed against any
+/// base URL. The `headers` are those of the request, with the API key, signature and timestamp
+/// redacted, so cassettes can be committed. The `body` of the request and the `response` are
+/// stored as strings.
+///
+/// Request bodies are stored as sent, and may hold details such as the address of a withdrawal.
+/// Use [`with_body_redaction`](CassetteTransport::with_body_redaction) to redact them before
+/// committing a cassette.
+///
+/// When replaying, a request is answered with the first interaction not yet replayed that has the
+/// same method, path and body. Headers are not compared, since signatures change with every
+/// request. A request without an interaction is answered with a `501` carrying an error with the
+/// code [`NO_RESPONSE_CODE`](super::NO_RESPONSE_CODE), which names the request.
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::testing::CassetteTransport;
+///
+/// // Record against the API once...
+/// let transport = CassetteTransport::record("time.jsonl", reqwest::Client::new()).unwrap();
+/// let c = bitvavo::Client::new().with_transport(transport);
+/// let t = c.time().await.unwrap();
+///
+/// // ...and replay the same responses offline afterwards.
+/// let transport = CassetteTransport::replay("time.jsonl").unwrap();
+/// let c = bitvavo::Client::new().with_transport(transport);
+/// assert_eq!(c.time().await.unwrap(), t);
+/// # })
+/// ```
+pub struct CassetteTransport {
+    mode: Mode,
+    redact_body: Option<Box<Redact>>,
+}
+
+enum Mode {
+    Record {
+        inner: Box<dyn Transport>,
+        file: Mutex<File>,
+    },
+    Replay {
+        interactions: Vec<Interaction>,
+        replayed: Mutex<Vec<bool>>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    path: String,
+    headers: BTreeMap<String, String>,
+    body: String,
+    status: u16,
+    response_headers: BTreeMap<String, String>,
+    response: String,
+}
+
+impl CassetteTransport {
+    /// Record to the cassette at `path`, sending requests using `inner`. The file is created, or
+    /// truncated if it exists.
+    ///
+    /// Interactions are written as their responses arrive. An interaction that cannot be
+    /// written is logged as a warning and left out, and recording carries on with the next.
+    pub fn record(path: impl AsRef<Path>, inner: impl Transport + 'static) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            mode: Mode::Record {
+                inner: Box::new(inner),
+                file: Mutex::new(file),
+            },
+            redact_body: None,
+        })
+    }
+
+    /// Replay the cassette at `path`.
+    pub fn replay(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        let mut interactions = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            interactions.push(serde_json::from_str(&line)?);
+        }
+
+        let replayed = Mutex::new(vec![false; interactions.len()]);
+        Ok(Self {
+            mode: Mode::Replay {
+                interactions,
+                replayed,
+            },
+            redact_body: None,
+        })
+    }
+
+    /// Pass request bodies through `redact` before they are recorded, or matched against the
+    /// recorded ones when replaying. The same redaction has to be used for both.
+    ///
+    /// ```no_run
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::testing::CassetteTransport;
+    ///
+    /// let transport = CassetteTransport::record("withdraw.jsonl", reqwest::Client::new())
+    ///     .unwrap()
+    ///     .with_body_redaction(|body| body.replace("MyWalletAddress", "***"));
+    /// ```
+    pub fn with_body_redaction(
+        mut self,
+        redact: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.redact_body = Some(Box::new(redact));
+        self
+    }
+
+    fn body_of(&self, request: &reqwest::Request) -> String {
+        let body = request.body().and_then(|body| body.as_bytes());
+        let body = String::from_utf8_lossy(body.unwrap_or_default());
+        match &self.redact_body {
+            Some(redact) => redact(&body),
+            None => body.into_owned(),
+        }
+    }
+}
+
+impl Transport for CassetteTransport {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, reqwest::Result<reqwest::Response>> {
+        match &self.mode {
+            Mode::Record { inner, file } => Box::pin(async move {
+                let method = request.method().to_string();
+                let path = path_of(&request);
+                let body = self.body_of(&request);
+                let headers = request
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                            "***".to_string()
+                        } else {
+                            String::from_utf8_lossy(value.as_bytes()).into_owned()
+                        };
+                        (name.to_string(), value)
+                    })
+                    .collect();
+
+                let response = inner.execute(request).await?;
+
+                let status = response.status().as_u16();
+                let response_headers = response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                        (name.to_string(), value)
+                    })
+                    .collect();
+                let response = String::from_utf8_lossy(&response.bytes().await?).into_owned();
+
+                let interaction = Interaction {
+                    method,
+                    path,
+                    headers,
+                    body,
+                    status,
+                    response_headers,
+                    response,
+                };
+
+                let line = serde_json::to_string(&interaction)
+                    .expect("Serializing an interaction should succeed");
+                let mut file = file.lock().unwrap();
+                if let Err(err) = writeln!(file, "{line}") {
+                    tracing::warn!(
+                        path = interaction.path,
+                        %err,
+                        "failed to write an interaction to the cassette"
+                    );
+                }
+
+                Ok(to_response(&interaction))
+            }),
+            Mode::Replay {
+                interactions,
+                replayed,
+            } => {
+                let mut replayed = replayed.lock().unwrap();
+
+                let method = request.method().as_str();
+                let path = path_of(&request);
+                let body = self.body_of(&request);
+                let found = interactions.iter().enumerate().find(|(i, interaction)| {
+                    !replayed[*i]
+                        && interaction.method == method
+                        && interaction.path == path
+                        && interaction.body == body
+                });
+
+                let response = match found {
+                    Some((i, interaction)) => {
+                        replayed[i] = true;
+                        to_response(interaction)
+                    }
+                    None => super::no_response(request.method(), &path_of(&request)).into(),
+                };
+
+                Box::pin(async move { Ok(response) })
+            }
+        }
+    }
+}
+
+fn path_of(request: &reqwest::Request) -> String {
+    let url = request.url();
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn to_response(interaction: &Interaction) -> reqwest::Response {
+    let mut builder = http::Response::builder().status(interaction.status);
+    for (name, value) in &interaction.response_headers {
+        builder = builder.header(name, value);
+    }
+
+    builder
+        .body(interaction.response.clone())
+        .expect("A replayed response should be valid")
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::types::WithdrawOrder;
+    use crate::Client;
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/balance"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"symbol": "BTC", "available": "1.5", "inOrder": "0.5"},
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cassette =
+            std::env::temp_dir().join(format!("bitvavo-cassette-{}.jsonl", std::process::id()));
+
+        let transport = CassetteTransport::record(&cassette, reqwest::Client::new())
+            .expect("Creating the cassette should succeed");
+        let client = Client::with_credentials("my-key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap()
+            .with_transport(transport);
+        let recorded = client
+            .balance("BTC")
+            .await
+            .expect("Getting the balance should succeed");
+
+        let contents = std::fs::read_to_string(&cassette).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(!contents.contains("my-key"));
+
+        let interaction: Interaction = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(interaction.path, "/v2/balance?symbol=BTC");
+        assert_eq!(interaction.headers["bitvavo-access-key"], "***");
+        assert_eq!(interaction.headers["bitvavo-access-signature"], "***");
+
+        // Replaying needs neither the server nor the same base URL.
+        let transport =
+            CassetteTransport::replay(&cassette).expect("Reading the cassette should succeed");
+        let client = Client::with_credentials("other-key".to_string(), "secret".to_string())
+            .with_transport(transport);
+        let replayed = client
+            .balance("BTC")
+            .await
+            .expect("Replaying the balance should succeed");
+        assert_eq!(replayed.available, recorded.available);
+
+        // Each interaction is only replayed once.
+        let err = client
+            .balance("BTC")
+            .await
+            .expect_err("A request without a recording should fail");
+        assert!(matches!(
+            err,
+            crate::Error::Bitvavo { code: crate::testing::NO_RESPONSE_CODE, ref message }
+                if message.starts_with("No response for GET /v2/balance")
+        ));
+
+        std::fs::remove_file(&cassette).unwrap();
+    }
+
+    #[tokio::test]
+    async fn redact_bodies() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/withdrawal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "symbol": "BTC",
+                "amount": "0.1",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cassette = std::env::temp_dir().join(format!(
+            "bitvavo-cassette-redacted-{}.jsonl",
+            std::process::id()
+        ));
+        let redact = |body: &str| body.replace("MyWalletAddress", "***");
+        let order = || WithdrawOrder {
+            symbol: String::from("BTC"),
+            amount: String::from("0.1"),
+            address: String::from("MyWalletAddress"),
+            payment_id: None,
+            internal: false,
+            add_withdrawal_fee: false,
+        };
+
+        let transport = CassetteTransport::record(&cassette, reqwest::Client::new())
+            .expect("Creating the cassette should succeed")
+            .with_body_redaction(redact);
+        let client = Client::with_credentials("my-key".to_string(), "secret".to_string())
+            .with_base_url(server.uri().parse().unwrap())
+            .unwrap()
+            .with_transport(transport);
+        client
+            .withdraw(order())
+            .await
+            .expect("Withdrawing should succeed");
+
+        let contents = std::fs::read_to_string(&cassette).unwrap();
+        assert!(!contents.contains("MyWalletAddress"));
+
+        let transport = CassetteTransport::replay(&cassette)
+            .expect("Reading the cassette should succeed")
+            .with_body_redaction(redact);
+        let client = Client::with_credentials("my-key".to_string(), "secret".to_string())
+            .with_transport(transport);
+        let response = client
+            .withdraw(order())
+            .await
+            .expect("Replaying the withdrawal should succeed");
+        assert!(response.success);
+
+        std::fs::remove_file(&cassette).unwrap();
+    }
+}
@@ -0,0 +1,26 @@
+use futures_util::future::BoxFuture;
+
+/// The means by which requests are sent to the API.
+///
+/// The [`Client`] builds and signs each request, and hands it to its transport to be executed. By
+/// default this is a [`reqwest::Client`], but it can be replaced using [`Client::with_transport`],
+/// for instance to serve canned responses in tests.
+///
+/// [`Client`]: crate::Client
+/// [`Client::with_transport`]: crate::Client::with_transport
+pub trait Transport: Send + Sync {
+    /// Execute a request, returning the response to it.
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, reqwest::Result<reqwest::Response>>;
+}
+
+impl Transport for reqwest::Client {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, reqwest::Result<reqwest::Response>> {
+        Box::pin(reqwest::Client::execute(self, request))
+    }
+}
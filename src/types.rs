@@ -1,12 +1,26 @@
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use serde::de::{Error, SeqAccess, Unexpected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use uuid::Uuid;
 
+use crate::InvalidOrder;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::{Decimal, RoundingStrategy};
+
 /// Time interval between each candlestick.
-#[derive(Debug)]
+///
+/// The exchange aligns candles to multiples of the interval since the Unix epoch, so every
+/// candle starts on a UTC boundary. In particular, daily candles run from midnight to midnight
+/// UTC, regardless of where the caller is. See [`Client::candles_for_local_day`] for fetching
+/// the candles making up a day in another timezone.
+///
+/// [`Client::candles_for_local_day`]: crate::Client::candles_for_local_day
+#[derive(Debug, Clone, Copy)]
 pub enum CandleInterval {
     OneMinute,
     FiveMinutes,
@@ -21,21 +35,98 @@ pub enum CandleInterval {
     OneDay,
 }
 
-impl fmt::Display for CandleInterval {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl CandleInterval {
+    /// The string used for the interval by the API, e.g. `"1h"`.
+    pub fn as_str(&self) -> &'static str {
         match self {
-            CandleInterval::OneMinute => write!(f, "1m"),
-            CandleInterval::FiveMinutes => write!(f, "5m"),
-            CandleInterval::FifteenMinutes => write!(f, "15m"),
-            CandleInterval::ThirtyMinutes => write!(f, "30m"),
-            CandleInterval::OneHour => write!(f, "1h"),
-            CandleInterval::TwoHours => write!(f, "2h"),
-            CandleInterval::FourHours => write!(f, "4h"),
-            CandleInterval::SixHours => write!(f, "6h"),
-            CandleInterval::EightHours => write!(f, "8h"),
-            CandleInterval::TwelveHours => write!(f, "12h"),
-            CandleInterval::OneDay => write!(f, "1d"),
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::FifteenMinutes => "15m",
+            CandleInterval::ThirtyMinutes => "30m",
+            CandleInterval::OneHour => "1h",
+            CandleInterval::TwoHours => "2h",
+            CandleInterval::FourHours => "4h",
+            CandleInterval::SixHours => "6h",
+            CandleInterval::EightHours => "8h",
+            CandleInterval::TwelveHours => "12h",
+            CandleInterval::OneDay => "1d",
+        }
+    }
+
+    /// The length of the interval.
+    pub fn duration(&self) -> Duration {
+        let minutes = match self {
+            CandleInterval::OneMinute => 1,
+            CandleInterval::FiveMinutes => 5,
+            CandleInterval::FifteenMinutes => 15,
+            CandleInterval::ThirtyMinutes => 30,
+            CandleInterval::OneHour => 60,
+            CandleInterval::TwoHours => 2 * 60,
+            CandleInterval::FourHours => 4 * 60,
+            CandleInterval::SixHours => 6 * 60,
+            CandleInterval::EightHours => 8 * 60,
+            CandleInterval::TwelveHours => 12 * 60,
+            CandleInterval::OneDay => 24 * 60,
+        };
+        Duration::from_secs(minutes * 60)
+    }
+}
+
+/// The start and end of a calendar day in the given timezone, in milliseconds since the Unix
+/// epoch. The start is inclusive and the end exclusive.
+///
+/// The day is not always 24 hours long: across daylight saving transitions it is 23 or 25. Where
+/// a transition skips local midnight, the day starts at the first local time that exists.
+///
+/// Returns `None` if the day starts before the epoch, or if it is the last day chrono can
+/// represent.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::local_day_bounds;
+/// use chrono::NaiveDate;
+/// use chrono_tz::Europe::Amsterdam;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+/// let (start, end) = local_day_bounds(Amsterdam, date).unwrap();
+///
+/// assert_eq!(end - start, 23 * 60 * 60 * 1000);
+/// ```
+#[cfg(feature = "chrono-tz")]
+pub fn local_day_bounds(tz: chrono_tz::Tz, date: chrono::NaiveDate) -> Option<(u64, u64)> {
+    use chrono::TimeZone;
+
+    let start_of = |date: chrono::NaiveDate| {
+        let mut time = date.and_time(chrono::NaiveTime::MIN);
+        loop {
+            if let Some(start) = tz.from_local_datetime(&time).earliest() {
+                return u64::try_from(start.timestamp_millis()).ok();
+            }
+            time = time.checked_add_signed(chrono::Duration::minutes(15))?;
         }
+    };
+
+    Some((start_of(date)?, start_of(date.succ_opt()?)?))
+}
+
+impl fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for CandleInterval {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Serialize for CandleInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
     }
 }
 
@@ -50,6 +141,89 @@ pub struct OHLCV {
     pub volume: String,
 }
 
+/// Find the candles missing from a series, which the exchange leaves out for intervals without
+/// any trades.
+///
+/// Each gap is returned as the start of its first missing candle and the start of the candle
+/// after it, in milliseconds since the Unix epoch, oldest first. The candles may be in either
+/// order, and only gaps between the first and last of them are found.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::{detect_gaps, CandleInterval, OHLCV};
+///
+/// let candles: Vec<OHLCV> = serde_json::from_str(
+///     r#"[[180000, "1", "1", "1", "1", "1"], [60000, "1", "1", "1", "1", "1"]]"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(detect_gaps(&candles, CandleInterval::OneMinute), [(120000, 180000)]);
+/// ```
+pub fn detect_gaps(candles: &[OHLCV], interval: CandleInterval) -> Vec<(u64, u64)> {
+    let step = interval.duration().as_millis() as u64;
+
+    let mut times: Vec<_> = candles.iter().map(|candle| candle.time).collect();
+    times.sort_unstable();
+    times.dedup();
+
+    times
+        .windows(2)
+        .filter(|pair| pair[1] - pair[0] > step)
+        .map(|pair| (pair[0] + step, pair[1]))
+        .collect()
+}
+
+/// A candlestick with numeric values, as converted by [`OHLCV::to_numeric`].
+#[cfg(feature = "decimal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumericCandle {
+    /// The start of the candle, in milliseconds since the Unix epoch.
+    pub time: u64,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+}
+
+#[cfg(feature = "decimal")]
+impl OHLCV {
+    /// Convert the candle to numeric values.
+    ///
+    /// Fails with [`Error::InvalidNumber`] naming the first field that cannot be parsed.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::OHLCV;
+    ///
+    /// let candle: OHLCV =
+    ///     serde_json::from_str(r#"[1548684000000, "3000", "3100", "2900", "3050", "12.5"]"#)
+    ///         .unwrap();
+    /// let candle = candle.to_numeric().unwrap();
+    ///
+    /// assert!(candle.high > candle.low);
+    /// ```
+    ///
+    /// [`Error::InvalidNumber`]: crate::Error::InvalidNumber
+    pub fn to_numeric(&self) -> crate::Result<NumericCandle> {
+        let parse = |field, value: &str| {
+            parse_decimal(value).ok_or_else(|| crate::Error::InvalidNumber {
+                field,
+                value: value.to_string(),
+            })
+        };
+
+        Ok(NumericCandle {
+            time: self.time,
+            open: parse("open", &self.open)?,
+            high: parse("high", &self.high)?,
+            low: parse("low", &self.low)?,
+            close: parse("close", &self.close)?,
+            volume: parse("volume", &self.volume)?,
+        })
+    }
+}
+
 macro_rules! next_seq_element {
     ($seq:ident, $name:ident) => {
         $seq.next_element()?
@@ -90,8 +264,225 @@ impl<'de> Deserialize<'de> for OHLCV {
     }
 }
 
+/// Deserialize a field that is expected to be a string, but may be sent as a number.
+///
+/// Numbers are normalized to their string representation. Non-integer numbers arrive from the
+/// JSON parser as an `f64`. With the `decimal` feature they are converted straight into a
+/// [`Decimal`], so the string is one the decimal accessors parse back exactly, without the noise
+/// digits a float can print. Without it they are printed as an `f64`, and may lose precision.
+pub fn de_string_or_number<'de, D>(deserializer: D) -> crate::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrNumberVisitor;
+
+    impl<'de> Visitor<'de> for StringOrNumberVisitor {
+        type Value = String;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a string or a number")
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> crate::Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_string<E: Error>(self, v: String) -> crate::Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_u64<E: Error>(self, v: u64) -> crate::Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_i64<E: Error>(self, v: i64) -> crate::Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_f64<E: Error>(self, v: f64) -> crate::Result<Self::Value, E> {
+            #[cfg(feature = "decimal")]
+            if let Ok(v) = Decimal::try_from(v) {
+                return Ok(v.normalize().to_string());
+            }
+
+            Ok(v.to_string())
+        }
+    }
+
+    deserializer.deserialize_any(StringOrNumberVisitor)
+}
+
+/// Like [`de_string_or_number`], for optional fields.
+pub fn de_opt_string_or_number<'de, D>(deserializer: D) -> crate::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringOrNumber(String);
+
+    impl<'de> Deserialize<'de> for StringOrNumber {
+        fn deserialize<D>(deserializer: D) -> crate::Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            de_string_or_number(deserializer).map(StringOrNumber)
+        }
+    }
+
+    let s = Option::<StringOrNumber>::deserialize(deserializer)?;
+    Ok(s.map(|s| s.0))
+}
+
+/// Deserialize an optional UUID, treating an empty string as absent.
+pub fn de_opt_uuid<'de, D>(deserializer: D) -> crate::Result<Option<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)?.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => Uuid::parse_str(s)
+            .map(Some)
+            .map_err(|_| D::Error::invalid_value(Unexpected::Str(s), &"a UUID")),
+    }
+}
+
+/// The symbol of an asset, e.g. `BTC`.
+///
+/// Methods taking a symbol accept a `&str` or `String` as well, so the type mostly serves to tell
+/// symbols apart from [market pairs](MarketPair) where both are passed around.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Symbol(String);
+
+impl Symbol {
+    /// The symbol as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(symbol: &str) -> Self {
+        Self(symbol.to_string())
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(symbol: &String) -> Self {
+        Self(symbol.clone())
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(symbol: String) -> Self {
+        Self(symbol)
+    }
+}
+
+impl From<&Symbol> for Symbol {
+    fn from(symbol: &Symbol) -> Self {
+        symbol.clone()
+    }
+}
+
+/// A market pair, made of a base and a quote asset, e.g. `BTC-EUR`.
+///
+/// Methods taking a market accept a `&str` or `String` as well.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MarketPair(String);
+
+impl MarketPair {
+    /// Create the pair trading `base` against `quote`.
+    pub fn new(base: impl AsRef<str>, quote: impl AsRef<str>) -> Self {
+        Self(format!("{}-{}", base.as_ref(), quote.as_ref()))
+    }
+
+    /// The base asset, before the `-`. This is the whole pair if it cannot be split, as by
+    /// [`split_market_pair`].
+    pub fn base(&self) -> &str {
+        split_market_pair(&self.0).map_or(&self.0, |(base, _)| base)
+    }
+
+    /// The quote asset, after the `-`. This is empty if the pair cannot be split, as by
+    /// [`split_market_pair`].
+    pub fn quote(&self) -> &str {
+        split_market_pair(&self.0).map_or("", |(_, quote)| quote)
+    }
+
+    /// The pair as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for MarketPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for MarketPair {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for MarketPair {
+    fn from(pair: &str) -> Self {
+        Self(pair.to_string())
+    }
+}
+
+impl From<&String> for MarketPair {
+    fn from(pair: &String) -> Self {
+        Self(pair.clone())
+    }
+}
+
+impl From<String> for MarketPair {
+    fn from(pair: String) -> Self {
+        Self(pair)
+    }
+}
+
+impl From<&MarketPair> for MarketPair {
+    fn from(pair: &MarketPair) -> Self {
+        pair.clone()
+    }
+}
+
+/// Split a market pair such as `BTC-EUR` into its base and quote assets.
+///
+/// Returns `None` unless the pair is made of two non-empty parts separated by a single `-`.
+///
+/// ```
+/// use bitvavo_api::types::split_market_pair;
+///
+/// assert_eq!(split_market_pair("BTC-EUR"), Some(("BTC", "EUR")));
+/// assert_eq!(split_market_pair("BTCEUR"), None);
+/// ```
+pub fn split_market_pair(pair: &str) -> Option<(&str, &str)> {
+    let (base, quote) = pair.split_once('-')?;
+    if base.is_empty() || quote.is_empty() || quote.contains('-') {
+        return None;
+    }
+    Some((base, quote))
+}
+
 /// Asset supported by Bitvavo.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Asset {
     pub symbol: String,
@@ -107,8 +498,107 @@ pub struct Asset {
     pub message: Option<String>,
 }
 
+impl Asset {
+    /// Estimate how long a deposit takes to be credited, from the number of confirmations it
+    /// needs and the typical block time of the asset's network, as given by
+    /// [`BlockTimes::default`].
+    ///
+    /// Returns `None` if the block time of the network is unknown.
+    pub fn estimated_deposit_time(&self) -> Option<Duration> {
+        self.estimated_deposit_time_with(&BlockTimes::default())
+    }
+
+    /// Estimate how long a deposit takes to be credited, using the given block times.
+    ///
+    /// The first of the asset's networks is used. A `Mainnet` network is looked up by the
+    /// asset's symbol, and any other by its name, such as `ERC20` for tokens on Ethereum.
+    pub fn estimated_deposit_time_with(&self, block_times: &BlockTimes) -> Option<Duration> {
+        let chain = match self.networks.first().map(String::as_str) {
+            None | Some("Mainnet") => &self.symbol,
+            Some(network) => network,
+        };
+
+        let block_time = block_times.get(chain)?;
+        let confirmations = u32::try_from(self.deposit_confirmations).ok()?;
+
+        block_time.checked_mul(confirmations)
+    }
+}
+
+/// Typical block times of blockchains, keyed by the symbol of their native asset or the name of
+/// the network.
+///
+/// The default table covers common chains:
+///
+/// | Chain                   | Block time  |
+/// |-------------------------|-------------|
+/// | `BTC`, `BCH`            | 10 minutes  |
+/// | `LTC`                   | 2.5 minutes |
+/// | `DOGE`                  | 1 minute    |
+/// | `ADA`                   | 20 seconds  |
+/// | `ETH`, `ERC20`          | 12 seconds  |
+/// | `DOT`                   | 6 seconds   |
+/// | `XRP`, `XLM`            | 4 seconds   |
+/// | `BEP20`, `TRX`, `TRC20` | 3 seconds   |
+/// | `SOL`                   | 1 second    |
+///
+/// Entries can be added or overridden with [`BlockTimes::with`].
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::BlockTimes;
+///
+/// let block_times = BlockTimes::default().with("AVAX", Duration::from_secs(2));
+///
+/// assert_eq!(block_times.get("AVAX"), Some(Duration::from_secs(2)));
+/// assert_eq!(block_times.get("BTC"), Some(Duration::from_secs(600)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockTimes(HashMap<String, Duration>);
+
+impl BlockTimes {
+    /// Set the block time of a chain, replacing any it had.
+    pub fn with(mut self, chain: impl Into<String>, block_time: Duration) -> Self {
+        self.0.insert(chain.into(), block_time);
+        self
+    }
+
+    /// The block time of a chain, if known.
+    pub fn get(&self, chain: &str) -> Option<Duration> {
+        self.0.get(chain).copied()
+    }
+}
+
+impl Default for BlockTimes {
+    fn default() -> Self {
+        let secs = Duration::from_secs;
+        [
+            ("BTC", secs(600)),
+            ("BCH", secs(600)),
+            ("LTC", secs(150)),
+            ("DOGE", secs(60)),
+            ("ADA", secs(20)),
+            ("ETH", secs(12)),
+            ("ERC20", secs(12)),
+            ("DOT", secs(6)),
+            ("XRP", secs(4)),
+            ("XLM", secs(4)),
+            ("BEP20", secs(3)),
+            ("TRX", secs(3)),
+            ("TRC20", secs(3)),
+            ("SOL", secs(1)),
+        ]
+        .into_iter()
+        .fold(Self(HashMap::new()), |table, (chain, block_time)| {
+            table.with(chain, block_time)
+        })
+    }
+}
+
 /// The status of an asset.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum AssetStatus {
     Ok,
     Maintenance,
@@ -135,7 +625,7 @@ impl<'de> Deserialize<'de> for AssetStatus {
 }
 
 /// Information about a market on Bitvavo.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Market {
     #[serde(rename = "market")]
@@ -151,8 +641,279 @@ pub struct Market {
     pub order_types: Vec<String>,
 }
 
+/// How a value is rounded to a precision.
+#[cfg(feature = "decimal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Truncate, rounding towards zero.
+    ToZero,
+    /// Round away from zero.
+    AwayFromZero,
+    /// Round to the nearest value, with midpoints away from zero.
+    NearestAwayFromZero,
+    /// Round to the nearest value, with midpoints to the even neighbour.
+    NearestEven,
+}
+
+#[cfg(feature = "decimal")]
+impl From<RoundingMode> for RoundingStrategy {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::ToZero => RoundingStrategy::ToZero,
+            RoundingMode::AwayFromZero => RoundingStrategy::AwayFromZero,
+            RoundingMode::NearestAwayFromZero => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::NearestEven => RoundingStrategy::MidpointNearestEven,
+        }
+    }
+}
+
+/// The rounding used by the precision helpers, such as [`base_to_quote`] and
+/// [`Market::price_ladder`], with a mode for prices and one for amounts.
+///
+/// By default amounts are truncated, so an order never exceeds what was meant to be spent or
+/// sold, and prices are rounded to the nearest value. Pass a different one to the `_with`
+/// variants of the helpers to round otherwise.
+#[cfg(feature = "decimal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounding {
+    /// The rounding of prices. Defaults to [`RoundingMode::NearestAwayFromZero`].
+    pub price: RoundingMode,
+    /// The rounding of amounts, of either asset. Defaults to [`RoundingMode::ToZero`].
+    pub amount: RoundingMode,
+}
+
+#[cfg(feature = "decimal")]
+impl Default for Rounding {
+    fn default() -> Self {
+        Self {
+            price: RoundingMode::NearestAwayFromZero,
+            amount: RoundingMode::ToZero,
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl Market {
+    /// Evenly spaced prices from `from` to `to`, both included, as for the orders of a grid.
+    ///
+    /// Each price is rounded to the nearest value with the market's price precision, in
+    /// significant digits. Prices are descending when `from` is above `to`. Neighbouring prices
+    /// that round to the same value are included once, so fewer than `steps` prices are returned
+    /// if the range is too narrow for the precision. A single step yields just `from`, and no
+    /// steps yield no prices.
+    ///
+    /// Returns [`Error::InvalidNumber`](crate::Error::InvalidNumber) if computing a price
+    /// overflows.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::Market;
+    /// use bitvavo::Decimal;
+    ///
+    /// # let market: Market = serde_json::from_str(r#"{"market":"BTC-EUR","status":"trading",
+    /// #     "base":"BTC","quote":"EUR","pricePrecision":5,"minOrderInBaseAsset":"0.0001",
+    /// #     "minOrderInQuoteAsset":"5","maxOrderInBaseAsset":"1000","maxOrderInQuoteAsset":"1000",
+    /// #     "orderTypes":["limit"]}"#).unwrap();
+    /// let prices = market
+    ///     .price_ladder(Decimal::from(30000), Decimal::from(31000), 5)
+    ///     .unwrap();
+    ///
+    /// let expected = [30000, 30250, 30500, 30750, 31000].map(Decimal::from);
+    /// assert_eq!(prices, expected);
+    /// ```
+    pub fn price_ladder(
+        &self,
+        from: Decimal,
+        to: Decimal,
+        steps: usize,
+    ) -> crate::Result<Vec<Decimal>> {
+        self.price_ladder_with(from, to, steps, Rounding::default())
+    }
+
+    /// Like [`price_ladder`](Market::price_ladder), rounding with the price mode of `rounding`.
+    pub fn price_ladder_with(
+        &self,
+        from: Decimal,
+        to: Decimal,
+        steps: usize,
+        rounding: Rounding,
+    ) -> crate::Result<Vec<Decimal>> {
+        let invalid = |field, value: &dyn fmt::Display| crate::Error::InvalidNumber {
+            field,
+            value: value.to_string(),
+        };
+
+        // The step is negative for a descending ladder.
+        let step = match steps {
+            0 => return Ok(Vec::new()),
+            1 => Decimal::ZERO,
+            _ => to
+                .checked_sub(from)
+                .and_then(|range| range.checked_div(Decimal::from(steps - 1)))
+                .ok_or_else(|| invalid("to", &to))?,
+        };
+
+        let mut prices = Vec::new();
+        for i in 0..steps {
+            // The last price is `to` exactly, rather than accumulating the error of `step`.
+            let price = match i {
+                i if i + 1 == steps && steps > 1 => to,
+                i => step
+                    .checked_mul(Decimal::from(i))
+                    .and_then(|offset| from.checked_add(offset))
+                    .ok_or_else(|| invalid("step", &step))?,
+            };
+            prices.extend(self.round_price(price, rounding.price));
+        }
+        prices.dedup();
+
+        Ok(prices)
+    }
+
+    /// Round a price to the market's price precision, in significant digits. Amounts are limited
+    /// by the decimals of their asset instead, see [`round_amount`].
+    fn round_price(&self, price: Decimal, mode: RoundingMode) -> Option<Decimal> {
+        let digits = u32::try_from(self.price_precision).ok()?;
+        price.round_sf_with_strategy(digits, mode.into())
+    }
+}
+
+/// Round an amount of an asset to `decimals`, the number of decimals of the asset as given by
+/// [`Asset::decimals`], with `mode`. At most 28 decimals are kept.
+///
+/// Bitvavo limits amounts by the decimals of their asset, and prices by the price precision of
+/// the market.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::{round_amount, RoundingMode};
+///
+/// let amount = "0.123456789".parse().unwrap();
+///
+/// assert_eq!(round_amount(amount, 8, RoundingMode::ToZero).to_string(), "0.12345678");
+/// ```
+#[cfg(feature = "decimal")]
+pub fn round_amount(amount: Decimal, decimals: u64, mode: RoundingMode) -> Decimal {
+    let decimals = decimals.min(28) as u32;
+    amount.round_dp_with_strategy(decimals, mode.into())
+}
+
+/// Convert an amount of the base asset to the quote asset at the given price.
+///
+/// The result is truncated to `quote_decimals`, the decimals of the quote asset as given by
+/// [`Asset::decimals`]. Returns `None` if the computation overflows.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::base_to_quote;
+///
+/// let quote = base_to_quote("0.5".parse().unwrap(), "30000.015".parse().unwrap(), 2);
+///
+/// assert_eq!(quote.unwrap().to_string(), "15000.00");
+/// ```
+#[cfg(feature = "decimal")]
+pub fn base_to_quote(amount: Decimal, price: Decimal, quote_decimals: u64) -> Option<Decimal> {
+    base_to_quote_with(amount, price, quote_decimals, Rounding::default())
+}
+
+/// Like [`base_to_quote`], rounding with the amount mode of `rounding`.
+#[cfg(feature = "decimal")]
+pub fn base_to_quote_with(
+    amount: Decimal,
+    price: Decimal,
+    quote_decimals: u64,
+    rounding: Rounding,
+) -> Option<Decimal> {
+    let quote = amount.checked_mul(price)?;
+    Some(round_amount(quote, quote_decimals, rounding.amount))
+}
+
+/// Convert an amount of the quote asset to the base asset at the given price.
+///
+/// The result is truncated to `base_decimals`, the decimals of the base asset as given by
+/// [`Asset::decimals`]. Returns `None` if the price is zero or the computation overflows.
+#[cfg(feature = "decimal")]
+pub fn quote_to_base(quote: Decimal, price: Decimal, base_decimals: u64) -> Option<Decimal> {
+    quote_to_base_with(quote, price, base_decimals, Rounding::default())
+}
+
+/// Like [`quote_to_base`], rounding with the amount mode of `rounding`.
+#[cfg(feature = "decimal")]
+pub fn quote_to_base_with(
+    quote: Decimal,
+    price: Decimal,
+    base_decimals: u64,
+    rounding: Rounding,
+) -> Option<Decimal> {
+    let amount = quote.checked_div(price)?;
+    Some(round_amount(amount, base_decimals, rounding.amount))
+}
+
+/// Format an amount with exactly `decimals` decimals, rounding with `mode` and padding with
+/// zeros. At most 28 decimals are shown.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::{format_amount, RoundingMode};
+///
+/// let amount = "1.23456789".parse().unwrap();
+///
+/// assert_eq!(format_amount(amount, 2, RoundingMode::ToZero), "1.23");
+/// assert_eq!(format_amount(amount, 10, RoundingMode::ToZero), "1.2345678900");
+/// ```
+#[cfg(feature = "decimal")]
+pub fn format_amount(amount: Decimal, decimals: u64, mode: RoundingMode) -> String {
+    let mut amount = round_amount(amount, decimals, mode);
+    amount.rescale(decimals.min(28) as u32);
+    amount.to_string()
+}
+
+/// The differences between two snapshots of the markets, as returned by
+/// [`Client::diff_markets`].
+///
+/// Markets are matched by pair. A market is changed if any of its information differs, such as
+/// its status or price precision.
+///
+/// [`Client::diff_markets`]: crate::Client::diff_markets
+#[derive(Debug, Default)]
+pub struct MarketDiff {
+    /// Markets only in the current snapshot.
+    pub added: Vec<Market>,
+    /// Markets only in the previous snapshot.
+    pub removed: Vec<Market>,
+    /// Markets in both snapshots that differ, as their previous and current information.
+    pub changed: Vec<(Market, Market)>,
+}
+
+impl MarketDiff {
+    /// Compute the differences from the `previous` snapshot of the markets to the `current` one.
+    pub fn between(previous: &[Market], current: &[Market]) -> Self {
+        let mut diff = Self::default();
+
+        for market in current {
+            match previous.iter().find(|m| m.pair == market.pair) {
+                None => diff.added.push(market.clone()),
+                Some(prev) if prev != market => diff.changed.push((prev.clone(), market.clone())),
+                Some(_) => {}
+            }
+        }
+        for market in previous {
+            if !current.iter().any(|m| m.pair == market.pair) {
+                diff.removed.push(market.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Whether the snapshots hold the same markets.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
 /// The status of a market.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarketStatus {
     Trading,
     Halted,
@@ -178,6 +939,39 @@ impl<'de> Deserialize<'de> for MarketStatus {
     }
 }
 
+/// How many levels of an order book to get.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Depth {
+    /// The full book, which is the default.
+    Full,
+    /// The given number of best levels on each side, capped at [`Depth::MAX`].
+    Levels(u16),
+}
+
+impl Depth {
+    /// The maximum number of levels the API returns on each side.
+    pub const MAX: u16 = 1000;
+
+    /// The depth to request, or `None` for the full book.
+    #[cfg(feature = "client")]
+    pub(crate) fn levels(self) -> Option<u16> {
+        match self {
+            Depth::Full => None,
+            Depth::Levels(levels) => Some(levels.min(Self::MAX)),
+        }
+    }
+}
+
+/// `None` is the full book, and `Some(depth)` that many levels, capped at [`Depth::MAX`].
+impl From<Option<u64>> for Depth {
+    fn from(depth: Option<u64>) -> Self {
+        match depth {
+            None => Depth::Full,
+            Some(depth) => Depth::Levels(depth.min(u64::from(Depth::MAX)) as u16),
+        }
+    }
+}
+
 /// Order book for a particular market.
 #[derive(Debug, Deserialize)]
 pub struct OrderBook {
@@ -187,6 +981,98 @@ pub struct OrderBook {
     pub asks: Vec<Quote>,
 }
 
+/// A side of the order book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+impl OrderBook {
+    /// Iterate over the levels of one side of the book, from best to worst price.
+    pub fn levels(&self, side: BookSide) -> impl Iterator<Item = &Quote> {
+        match side {
+            BookSide::Bid => self.bids.iter(),
+            BookSide::Ask => self.asks.iter(),
+        }
+    }
+
+    /// The number of levels in the book, as `(bids, asks)`.
+    pub fn depth(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+
+    /// Verify that `nonce` is the one directly following the nonce of this book.
+    ///
+    /// Bitvavo increments the nonce by one for every change to the book. A gap in the sequence
+    /// means updates were missed, and the locally maintained book should be resynchronized by
+    /// fetching a fresh snapshot. A book whose nonce is `u64::MAX` has no next nonce, so any
+    /// nonce is treated as a gap, reported as expecting `u64::MAX`.
+    pub fn verify_nonce(&self, nonce: u64) -> crate::Result<()> {
+        match self.nonce.checked_add(1) {
+            Some(expected) if nonce == expected => Ok(()),
+            expected => Err(crate::Error::NonceMismatch {
+                expected: expected.unwrap_or(u64::MAX),
+                received: nonce,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl OrderBook {
+    /// Estimate whether a market order for `amount` of the base asset would be rejected by market
+    /// protection, for moving the price by more than `max_slippage`.
+    ///
+    /// The order is walked through the side of the book it would take from, and the slippage is
+    /// the relative difference between the best price and the worst price it reaches, e.g. `0.01`
+    /// for 1%. An order the book is not deep enough to fill, or a book with levels that cannot be
+    /// parsed, is considered to trip protection.
+    ///
+    /// This is only an estimate based on this snapshot of the book, not the exact rule the
+    /// exchange applies.
+    pub fn would_trip_protection(
+        &self,
+        side: TradeSide,
+        amount: Decimal,
+        max_slippage: Decimal,
+    ) -> bool {
+        match self.price_range(side, amount) {
+            Some((best, worst)) => {
+                let slippage = (worst - best).abs().checked_div(best);
+                slippage.is_none_or(|slippage| slippage > max_slippage)
+            }
+            None => true,
+        }
+    }
+
+    /// The best and worst price reached by a market order for `amount` of the base asset, or
+    /// `None` if the book cannot fill it.
+    fn price_range(&self, side: TradeSide, amount: Decimal) -> Option<(Decimal, Decimal)> {
+        let levels = match side {
+            TradeSide::Buy => self.levels(BookSide::Ask),
+            TradeSide::Sell => self.levels(BookSide::Bid),
+        };
+
+        let mut best = None;
+        let mut remaining = amount;
+
+        for quote in levels {
+            let price = parse_decimal(&quote.price)?;
+            let size = parse_decimal(&quote.amount)?;
+
+            let best = *best.get_or_insert(price);
+            remaining -= size;
+
+            if remaining <= Decimal::ZERO {
+                return Some((best, price));
+            }
+        }
+
+        None
+    }
+}
+
 /// A quote in the order book.
 #[derive(Debug)]
 pub struct Quote {
@@ -223,65 +1109,256 @@ impl<'de> Deserialize<'de> for Quote {
     }
 }
 
-/// A trade performed on the exchange for a particular market.
+/// Order book for a particular market, with the quotes parsed into decimals.
+///
+/// This is parsed straight from the response, without allocating a string for every price and
+/// amount as [`OrderBook`] does, for consumers processing many levels.
+#[cfg(feature = "decimal")]
 #[derive(Debug, Deserialize)]
-pub struct Trade {
-    pub id: String,
-    pub timestamp: u64,
-    pub amount: String,
-    pub price: String,
-    pub side: TradeSide,
+pub struct NumericOrderBook {
+    pub market: String,
+    pub nonce: u64,
+    pub bids: Vec<NumericQuote>,
+    pub asks: Vec<NumericQuote>,
 }
 
-/// The side of a trade.
-#[derive(Debug)]
-pub enum TradeSide {
-    Buy,
-    Sell,
+/// A quote in the order book, parsed into decimals.
+#[cfg(feature = "decimal")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumericQuote {
+    pub price: Decimal,
+    pub amount: Decimal,
 }
 
-impl Serialize for TradeSide {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match self {
-            TradeSide::Buy => serializer.serialize_str("buy"),
-            TradeSide::Sell => serializer.serialize_str("sell"),
-        }
+#[cfg(feature = "decimal")]
+impl From<NumericQuote> for (Decimal, Decimal) {
+    fn from(quote: NumericQuote) -> Self {
+        (quote.price, quote.amount)
     }
 }
 
-impl<'de> Deserialize<'de> for TradeSide {
-    fn deserialize<D>(deserializer: D) -> crate::Result<TradeSide, D::Error>
+/// A decimal deserialized from a string, without taking ownership of it.
+#[cfg(feature = "decimal")]
+struct DecimalStr(Decimal);
+
+#[cfg(feature = "decimal")]
+impl<'de> Deserialize<'de> for DecimalStr {
+    fn deserialize<D>(deserializer: D) -> crate::Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
+        struct DecimalStrVisitor;
 
-        match s.as_str() {
-            "buy" => Ok(TradeSide::Buy),
+        impl Visitor<'_> for DecimalStrVisitor {
+            type Value = DecimalStr;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a decimal string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> crate::Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                parse_decimal(v)
+                    .map(DecimalStr)
+                    .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(DecimalStrVisitor)
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'de> Deserialize<'de> for NumericQuote {
+    fn deserialize<D>(deserializer: D) -> crate::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NumericQuoteVisitor;
+
+        impl<'de> Visitor<'de> for NumericQuoteVisitor {
+            type Value = NumericQuote;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "Quote")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> crate::Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let DecimalStr(price) = next_seq_element!(seq, price);
+                let DecimalStr(amount) = next_seq_element!(seq, amount);
+                Ok(NumericQuote { price, amount })
+            }
+        }
+
+        deserializer.deserialize_seq(NumericQuoteVisitor)
+    }
+}
+
+/// A trade performed on the exchange for a particular market.
+#[derive(Debug, Deserialize)]
+pub struct Trade {
+    pub id: String,
+    pub timestamp: u64,
+    pub amount: String,
+    pub price: String,
+    pub side: TradeSide,
+}
+
+impl Trade {
+    /// Merge pages of trades into a single list, ordered from oldest to newest.
+    ///
+    /// Trades appearing in more than one page, as happens when pages overlap, are only kept once.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::{Trade, TradeSide};
+    ///
+    /// let trade = |id: &str, timestamp| Trade {
+    ///     id: id.to_string(),
+    ///     timestamp,
+    ///     amount: String::from("0.1"),
+    ///     price: String::from("30000"),
+    ///     side: TradeSide::Buy,
+    /// };
+    ///
+    /// let trades = Trade::merge_sorted([
+    ///     vec![trade("c", 3), trade("b", 2)],
+    ///     vec![trade("b", 2), trade("a", 1)],
+    /// ]);
+    ///
+    /// let ids: Vec<_> = trades.iter().map(|t| t.id.as_str()).collect();
+    /// assert_eq!(ids, ["a", "b", "c"]);
+    /// ```
+    pub fn merge_sorted(pages: impl IntoIterator<Item = Vec<Trade>>) -> Vec<Trade> {
+        let mut trades: Vec<Trade> = pages.into_iter().flatten().collect();
+        trades.sort_unstable();
+        trades.dedup_by(|a, b| a.id == b.id);
+        trades
+    }
+}
+
+/// Trades are compared by their timestamp, and then by their ID.
+impl PartialEq for Trade {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Trade {}
+
+impl PartialOrd for Trade {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Trade {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.timestamp, &self.id).cmp(&(other.timestamp, &other.id))
+    }
+}
+
+/// The side of a trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    /// The string used for the side by the API, in bodies and query parameters alike.
+    pub fn as_query_str(&self) -> &'static str {
+        match self {
+            TradeSide::Buy => "buy",
+            TradeSide::Sell => "sell",
+        }
+    }
+}
+
+impl Serialize for TradeSide {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_query_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for TradeSide {
+    fn deserialize<D>(deserializer: D) -> crate::Result<TradeSide, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "buy" => Ok(TradeSide::Buy),
             "sell" => Ok(TradeSide::Sell),
             s => Err(D::Error::invalid_value(Unexpected::Str(s), &"[buy, sell]")),
         }
     }
 }
 
+/// A page of a list endpoint, with the cursor to request the next page with.
+///
+/// The cursor is `None` once the last page has been reached.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// A response together with when it was received, for judging how fresh it is.
+#[derive(Debug)]
+pub struct Timed<T> {
+    pub value: T,
+    /// When the response was received.
+    pub received_at: SystemTime,
+    /// The time in milliseconds since the Unix epoch the exchange reports the data is for, if
+    /// any.
+    pub server_time: Option<u64>,
+}
+
+impl<T> Timed<T> {
+    /// How old the data was when it was received, according to the server time.
+    ///
+    /// Returns `None` if there is no server time. This includes any clock skew between the
+    /// exchange and the local machine, and is zero if the server time is ahead.
+    pub fn age(&self) -> Option<Duration> {
+        let server_time = UNIX_EPOCH + Duration::from_millis(self.server_time?);
+        Some(
+            self.received_at
+                .duration_since(server_time)
+                .unwrap_or_default(),
+        )
+    }
+}
+
 /// A ticker for a given market pair.
 #[derive(Debug, Deserialize)]
 pub struct TickerPrice {
     pub market: String,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub price: Option<String>,
 }
 
 /// Highest buy and lowest sell prices currently available for a market.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TickerBook {
     pub market: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub bid: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub bid_size: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub ask: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub ask_size: Option<String>,
 }
 
@@ -292,20 +1369,102 @@ pub struct Ticker24h {
     pub market: String,
     pub start_timestamp: Option<u64>,
     pub timestamp: Option<u64>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub open: Option<String>,
     pub open_timestamp: Option<u64>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub high: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub low: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub last: Option<String>,
     pub close_timestamp: Option<u64>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub bid: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub bid_size: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub ask: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub ask_size: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub volume: Option<String>,
+    #[serde(default, deserialize_with = "de_opt_string_or_number")]
     pub volume_quote: Option<String>,
 }
 
+#[cfg(feature = "decimal")]
+impl Ticker24h {
+    /// The absolute change between the `open` and `last` prices.
+    ///
+    /// Returns `None` if either price is missing.
+    pub fn price_change(&self) -> Option<Decimal> {
+        let open = parse_decimal(self.open.as_deref()?)?;
+        let last = parse_decimal(self.last.as_deref()?)?;
+        last.checked_sub(open)
+    }
+
+    /// The change between the `open` and `last` prices, as a percentage of `open`.
+    ///
+    /// Returns `None` if either price is missing, or if `open` is zero.
+    pub fn price_change_percent(&self) -> Option<Decimal> {
+        let open = parse_decimal(self.open.as_deref()?)?;
+        let change = self.price_change()?;
+        change.checked_div(open)?.checked_mul(Decimal::ONE_HUNDRED)
+    }
+
+    /// The difference between the best `ask` and `bid` prices.
+    ///
+    /// Returns `None` if either price is missing.
+    pub fn spread(&self) -> Option<Decimal> {
+        let (bid, ask) = self.bid_ask()?;
+        ask.checked_sub(bid)
+    }
+
+    /// The midpoint between the best `bid` and `ask` prices.
+    ///
+    /// Returns `None` if either price is missing.
+    pub fn mid(&self) -> Option<Decimal> {
+        let (bid, ask) = self.bid_ask()?;
+        bid.checked_add(ask)?.checked_div(Decimal::TWO)
+    }
+
+    /// The spread in basis points of the [`mid`](Ticker24h::mid) price.
+    ///
+    /// Returns `None` if either price is missing, or if the mid price is zero.
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        let bps = Decimal::from(10_000);
+        self.spread()?.checked_div(self.mid()?)?.checked_mul(bps)
+    }
+
+    fn bid_ask(&self) -> Option<(Decimal, Decimal)> {
+        let bid = parse_decimal(self.bid.as_deref()?)?;
+        let ask = parse_decimal(self.ask.as_deref()?)?;
+        Some((bid, ask))
+    }
+}
+
+/// Parse a numeric string field into a decimal.
+///
+/// Numeric fields are kept as strings when deserializing, so an odd value never fails a whole
+/// response. Values that are not finite numbers, such as `NaN`, `Infinity` or an empty string,
+/// are treated as missing here, which makes every decimal accessor return `None` for them. A
+/// warning is logged when that happens, so such values don't go unnoticed.
+#[cfg(feature = "decimal")]
+pub(crate) fn parse_decimal(s: &str) -> Option<Decimal> {
+    let parsed = s.parse().ok();
+
+    #[cfg(feature = "client")]
+    if parsed.is_none() {
+        tracing::warn!(
+            value = s,
+            "numeric field is not a finite number, treating it as missing"
+        );
+    }
+
+    parsed
+}
+
 /// The fees for an account.
 #[derive(Debug, Deserialize)]
 pub struct Account {
@@ -315,8 +1474,11 @@ pub struct Account {
 /// The fees in use for an account.
 #[derive(Debug, Deserialize)]
 pub struct AccountFees {
+    #[serde(deserialize_with = "de_string_or_number")]
     pub taker: String,
+    #[serde(deserialize_with = "de_string_or_number")]
     pub maker: String,
+    #[serde(deserialize_with = "de_string_or_number")]
     pub volume: String,
 }
 
@@ -329,21 +1491,67 @@ pub struct Balance {
     pub in_order: String,
 }
 
+#[cfg(feature = "decimal")]
+impl Balance {
+    /// The total amount held, both available and in order.
+    ///
+    /// Returns `None` if either amount cannot be parsed.
+    pub fn total(&self) -> Option<Decimal> {
+        let available = parse_decimal(&self.available)?;
+        let in_order = parse_decimal(&self.in_order)?;
+        available.checked_add(in_order)
+    }
+}
+
 /// Fees charged for a market on an account.
 #[derive(Debug, Deserialize)]
 pub struct Fees {
     pub tier: u64,
+    #[serde(deserialize_with = "de_string_or_number")]
     pub volume: String,
+    #[serde(deserialize_with = "de_string_or_number")]
     pub taker: String,
+    #[serde(deserialize_with = "de_string_or_number")]
     pub maker: String,
 }
 
+#[cfg(feature = "decimal")]
+impl Fees {
+    /// The fee charged on a trade worth `notional` in the quote asset, as a maker or a taker.
+    ///
+    /// Returns `None` if the fee rate cannot be parsed.
+    pub fn fee_for(&self, notional: Decimal, is_maker: bool) -> Option<Decimal> {
+        let rate = if is_maker { &self.maker } else { &self.taker };
+        notional.checked_mul(parse_decimal(rate)?)
+    }
+}
+
+/// Where to deposit an asset to: an address for crypto assets, or a bank account for fiat.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum DepositInfo {
+    Crypto(CryptoDepositInfo),
+    Fiat(FiatDepositInfo),
+}
+
+/// The address to deposit a crypto asset to.
 #[derive(Debug, Deserialize)]
-pub struct DepositInfo {
+#[serde(rename_all = "camelCase")]
+pub struct CryptoDepositInfo {
     pub address: String,
     pub payment_id: Option<String>,
 }
 
+/// The bank account to deposit a fiat asset to, such as EUR.
+///
+/// The description must be included with the transfer, for it to be credited to the account.
+#[derive(Debug, Deserialize)]
+pub struct FiatDepositInfo {
+    pub iban: String,
+    pub bic: String,
+    pub description: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Deposit {
@@ -453,7 +1661,7 @@ pub struct WithdrawalOrderResponse {
     pub amount: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
     pub market: String,
@@ -473,8 +1681,566 @@ pub struct Order {
     pub response_required: bool,
 }
 
-/// The type of order.
+impl Order {
+    /// Check the amounts of a market order.
+    ///
+    /// A market buy spends an amount of the quote asset, and must therefore set `amount_quote`,
+    /// while a market sell sells an amount of the base asset, and must set `amount`. Market
+    /// orders setting both, neither, or the wrong one of the two are rejected. Other orders are
+    /// not checked.
+    ///
+    /// This is not done by [`Client::place_order`], so it has to be called explicitly.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::{OrderBuilder, OrderType, TradeSide};
+    ///
+    /// let order = OrderBuilder::market_buy("BTC-EUR", "100").build();
+    /// assert!(order.validate().is_ok());
+    ///
+    /// let order = OrderBuilder::new("BTC-EUR", TradeSide::Buy, OrderType::Market)
+    ///     .amount("0.1")
+    ///     .build();
+    /// assert!(order.validate().is_err());
+    /// ```
+    ///
+    /// [`Client::place_order`]: crate::Client::place_order
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.order_type != OrderType::Market {
+            return Ok(());
+        }
+
+        let err = match (&self.side, &self.amount, &self.amount_quote) {
+            (_, Some(_), Some(_)) => InvalidOrder::BothAmounts,
+            (_, None, None) => InvalidOrder::MissingAmount,
+            (TradeSide::Buy, Some(_), None) => InvalidOrder::AmountOnMarketBuy,
+            (TradeSide::Sell, None, Some(_)) => InvalidOrder::AmountQuoteOnMarketSell,
+            _ => return Ok(()),
+        };
+
+        Err(crate::Error::InvalidOrder(err))
+    }
+}
+
+/// A reference to an existing order, either by the id the exchange assigned to it or by the id
+/// the client set when placing it.
+///
+/// A [`Uuid`] converts into a reference by the exchange's id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderRef {
+    /// The `orderId` assigned by the exchange.
+    ById(Uuid),
+    /// The `clientOrderId` set when placing the order.
+    ByClientId(Uuid),
+}
+
+impl OrderRef {
+    /// The query parameter identifying the order.
+    #[cfg(feature = "client")]
+    pub(crate) fn query_param(self) -> (&'static str, String) {
+        match self {
+            OrderRef::ById(id) => ("orderId", id.to_string()),
+            OrderRef::ByClientId(id) => ("clientOrderId", id.to_string()),
+        }
+    }
+}
+
+impl From<Uuid> for OrderRef {
+    fn from(order_id: Uuid) -> Self {
+        OrderRef::ById(order_id)
+    }
+}
+
+/// A builder for an [`Order`], with sensible defaults for the optional fields.
+///
+/// By default the exchange is asked to respond with the full state of the order, and market
+/// protection is left enabled. Skipping the full response with
+/// [`response_required(false)`](OrderBuilder::response_required) makes placing an order
+/// slightly faster, at the cost of not learning its state, e.g. whether it was filled, from the
+/// response.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::{OrderBuilder, OrderType, TimeInForce, TradeSide};
+///
+/// let order = OrderBuilder::new("BTC-EUR", TradeSide::Buy, OrderType::Limit)
+///     .amount("0.1")
+///     .price("30000")
+///     .time_in_force(TimeInForce::GoodTillCancelled)
+///     .build();
+///
+/// assert!(order.response_required);
+/// assert!(!order.disable_market_protection);
+/// ```
+#[derive(Debug)]
+pub struct OrderBuilder {
+    order: Order,
+}
+
+impl OrderBuilder {
+    /// Start building an order of the given type, on the given side of a market.
+    pub fn new(market: impl Into<String>, side: TradeSide, order_type: OrderType) -> Self {
+        Self {
+            order: Order {
+                market: market.into(),
+                side,
+                order_type,
+                client_order_id: None,
+                amount: None,
+                amount_quote: None,
+                price: None,
+                trigger_amount: None,
+                trigger_type: None,
+                trigger_reference: None,
+                time_in_force: None,
+                post_only: None,
+                self_trade_prevention: None,
+                disable_market_protection: false,
+                response_required: true,
+            },
+        }
+    }
+
+    /// Start building a market buy, spending the given amount of the quote asset.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::OrderBuilder;
+    ///
+    /// // Buy as much BTC as 100 EUR gets.
+    /// let order = OrderBuilder::market_buy("BTC-EUR", "100").build();
+    ///
+    /// assert_eq!(order.amount_quote.as_deref(), Some("100"));
+    /// assert_eq!(order.amount, None);
+    /// ```
+    pub fn market_buy(market: impl Into<String>, amount_quote: impl Into<String>) -> Self {
+        Self::new(market, TradeSide::Buy, OrderType::Market).amount_quote(amount_quote)
+    }
+
+    /// Start building a market sell, selling the given amount of the base asset.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::OrderBuilder;
+    ///
+    /// // Sell 0.1 BTC for however much EUR it gets.
+    /// let order = OrderBuilder::market_sell("BTC-EUR", "0.1").build();
+    ///
+    /// assert_eq!(order.amount.as_deref(), Some("0.1"));
+    /// assert_eq!(order.amount_quote, None);
+    /// ```
+    pub fn market_sell(market: impl Into<String>, amount: impl Into<String>) -> Self {
+        Self::new(market, TradeSide::Sell, OrderType::Market).amount(amount)
+    }
+
+    /// Start building a market buy whose total outlay, including the taker fee, is
+    /// `total_quote`.
+    ///
+    /// The fee is charged on top of `amountQuote`, so this spends `total_quote / (1 + taker)`,
+    /// rounded down to `quote_decimals`, the decimals of the quote asset as given by
+    /// [`Asset::decimals`], so the outlay never exceeds `total_quote`. This is an
+    /// estimate against the given `fees`, and is off if the fee tier changes before the order
+    /// executes.
+    ///
+    /// Returns `None` if the taker fee cannot be parsed.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::{Fees, OrderBuilder};
+    /// use bitvavo::Decimal;
+    ///
+    /// let fees = Fees {
+    ///     tier: 0,
+    ///     volume: String::from("0"),
+    ///     taker: String::from("0.0025"),
+    ///     maker: String::from("0.0015"),
+    /// };
+    ///
+    /// // 100 / 1.0025 = 99.7506..., and a fee of 0.25 on 99.75 brings the outlay to 100.
+    /// let order = OrderBuilder::spend_inclusive("BTC-EUR", Decimal::from(100), 2, &fees)
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// assert_eq!(order.amount_quote.as_deref(), Some("99.75"));
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub fn spend_inclusive(
+        market: impl Into<String>,
+        total_quote: Decimal,
+        quote_decimals: u64,
+        fees: &Fees,
+    ) -> Option<Self> {
+        Self::spend_inclusive_with(
+            market,
+            total_quote,
+            quote_decimals,
+            fees,
+            Rounding::default(),
+        )
+    }
+
+    /// Like [`spend_inclusive`](OrderBuilder::spend_inclusive), rounding with the amount mode of
+    /// `rounding`. Rounding up may bring the outlay over `total_quote`.
+    #[cfg(feature = "decimal")]
+    pub fn spend_inclusive_with(
+        market: impl Into<String>,
+        total_quote: Decimal,
+        quote_decimals: u64,
+        fees: &Fees,
+        rounding: Rounding,
+    ) -> Option<Self> {
+        let taker = parse_decimal(&fees.taker)?;
+        let amount_quote = total_quote.checked_div(Decimal::ONE + taker)?;
+        let amount_quote = round_amount(amount_quote, quote_decimals, rounding.amount);
+
+        Some(Self::market_buy(market, amount_quote.to_string()))
+    }
+
+    /// Start building an order that executes immediately like a market order for `amount` of
+    /// the base asset of `market`, but never at a price more than `max_slippage` worse than the
+    /// best price in `book`, e.g. `0.01` for 1%.
+    ///
+    /// This is a safer alternative to a plain market order. The order is a limit order at the
+    /// protective price that is immediate-or-cancel, so whatever the book cannot fill within the
+    /// price is cancelled instead of filled at a worse price. The protective price is rounded to
+    /// the market's price precision, in significant digits, up for buys and down for sells, so
+    /// rounding never makes the order fill less than `max_slippage` allows.
+    ///
+    /// Returns [`Error::InvalidNumber`](crate::Error::InvalidNumber) unless `max_slippage` is at
+    /// least zero and below one, or if the side of the book the order takes from is empty, or
+    /// its best price cannot be parsed. Returns [`Error::InvalidOrder`](crate::Error::InvalidOrder)
+    /// if `book` is not the order book of `market`.
+    ///
+    /// ```
+    /// use bitvavo_api as bitvavo;
+    /// use bitvavo::types::{Market, OrderBook, OrderBuilder, TradeSide};
+    /// use bitvavo::Decimal;
+    ///
+    /// # let market: Market = serde_json::from_str(r#"{"market":"BTC-EUR","status":"trading",
+    /// #     "base":"BTC","quote":"EUR","pricePrecision":5,"minOrderInBaseAsset":"0.0001",
+    /// #     "minOrderInQuoteAsset":"5","maxOrderInBaseAsset":"1000","maxOrderInQuoteAsset":"1000",
+    /// #     "orderTypes":["limit"]}"#).unwrap();
+    /// let book: OrderBook = serde_json::from_str(
+    ///     r#"{"market":"BTC-EUR","nonce":1,"bids":[["30000","1"]],"asks":[["30010","1"]]}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// // Buy 0.5 BTC, paying at most 1% over the best ask of 30010.
+    /// let max_slippage = Decimal::new(1, 2);
+    /// let order = OrderBuilder::market_with_max_slippage(
+    ///     &market,
+    ///     TradeSide::Buy,
+    ///     Decimal::new(5, 1),
+    ///     &book,
+    ///     max_slippage,
+    /// )
+    /// .unwrap()
+    /// .build();
+    ///
+    /// assert_eq!(order.price.as_deref(), Some("30311"));
+    /// ```
+    #[cfg(feature = "decimal")]
+    pub fn market_with_max_slippage(
+        market: &Market,
+        side: TradeSide,
+        amount: Decimal,
+        book: &OrderBook,
+        max_slippage: Decimal,
+    ) -> crate::Result<Self> {
+        let invalid = |field, value: String| crate::Error::InvalidNumber { field, value };
+
+        if book.market != market.pair.as_str() {
+            return Err(crate::Error::InvalidOrder(
+                crate::InvalidOrder::BookMarketMismatch {
+                    market: market.pair.to_string(),
+                    book: book.market.clone(),
+                },
+            ));
+        }
+        if max_slippage.is_sign_negative() || max_slippage >= Decimal::ONE {
+            return Err(invalid("max_slippage", max_slippage.to_string()));
+        }
+
+        let (book_side, factor, mode) = match side {
+            TradeSide::Buy => (
+                BookSide::Ask,
+                Decimal::ONE + max_slippage,
+                RoundingMode::AwayFromZero,
+            ),
+            TradeSide::Sell => (
+                BookSide::Bid,
+                Decimal::ONE - max_slippage,
+                RoundingMode::ToZero,
+            ),
+        };
+
+        let best = book.levels(book_side).next().map(|level| &level.price);
+        let best = match best {
+            Some(price) => parse_decimal(price).ok_or_else(|| invalid("price", price.clone()))?,
+            None => return Err(invalid("price", String::new())),
+        };
+        let price = best
+            .checked_mul(factor)
+            .and_then(|price| market.round_price(price, mode))
+            .ok_or_else(|| invalid("price", best.to_string()))?;
+
+        let order = Self::new(market.pair.clone(), side, OrderType::Limit)
+            .amount(amount.to_string())
+            .price(price.normalize().to_string())
+            .time_in_force(TimeInForce::ImmediateOrCancel);
+        Ok(order)
+    }
+
+    /// Set an identifier of the client's choosing for the order.
+    pub fn client_order_id(mut self, client_order_id: Uuid) -> Self {
+        self.order.client_order_id = Some(client_order_id);
+        self
+    }
+
+    /// Set the amount of the base asset to trade.
+    pub fn amount(mut self, amount: impl Into<String>) -> Self {
+        self.order.amount = Some(amount.into());
+        self
+    }
+
+    /// Set the amount of the quote asset to trade.
+    pub fn amount_quote(mut self, amount_quote: impl Into<String>) -> Self {
+        self.order.amount_quote = Some(amount_quote.into());
+        self
+    }
+
+    /// Set the price of the order.
+    pub fn price(mut self, price: impl Into<String>) -> Self {
+        self.order.price = Some(price.into());
+        self
+    }
+
+    /// Set the amount at which the order is triggered.
+    pub fn trigger_amount(mut self, trigger_amount: impl Into<String>) -> Self {
+        self.order.trigger_amount = Some(trigger_amount.into());
+        self
+    }
+
+    /// Set the type of trigger for the order.
+    pub fn trigger_type(mut self, trigger_type: TriggerType) -> Self {
+        self.order.trigger_type = Some(trigger_type);
+        self
+    }
+
+    /// Set the price the trigger amount is compared to.
+    pub fn trigger_reference(mut self, trigger_reference: TriggerReference) -> Self {
+        self.order.trigger_reference = Some(trigger_reference);
+        self
+    }
+
+    /// Set how long the order should remain active.
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.order.time_in_force = Some(time_in_force);
+        self
+    }
+
+    /// Set whether the order may only be placed as a maker order.
+    pub fn post_only(mut self, post_only: bool) -> Self {
+        self.order.post_only = Some(post_only);
+        self
+    }
+
+    /// Set how to handle self trades.
+    pub fn self_trade_prevention(mut self, self_trade_prevention: SelfTradePrevention) -> Self {
+        self.order.self_trade_prevention = Some(self_trade_prevention);
+        self
+    }
+
+    /// Set whether to disable market protection. Defaults to `false`.
+    pub fn disable_market_protection(mut self, disable_market_protection: bool) -> Self {
+        self.order.disable_market_protection = disable_market_protection;
+        self
+    }
+
+    /// Set whether the exchange should respond with the full state of the order. Defaults to
+    /// `true`.
+    pub fn response_required(mut self, response_required: bool) -> Self {
+        self.order.response_required = response_required;
+        self
+    }
+
+    /// Build the order.
+    pub fn build(self) -> Order {
+        self.order
+    }
+}
+
+/// An order whose type determines the fields it must set, as an alternative to building an
+/// [`Order`] with [`OrderBuilder`].
+///
+/// Each variant holds exactly the fields its order type requires, so an order missing, say, the
+/// price of a limit order cannot be constructed. Optional settings, such as the time in force,
+/// are made on the [`OrderBuilder`] it converts into.
+///
+/// ```
+/// use bitvavo_api as bitvavo;
+/// use bitvavo::types::{Order, OrderBuilder, TimeInForce, TradeSide, TypedOrder};
+///
+/// let order = TypedOrder::Limit {
+///     market: "BTC-EUR".to_string(),
+///     side: TradeSide::Buy,
+///     amount: "0.1".to_string(),
+///     price: "30000".to_string(),
+/// };
+///
+/// let order = OrderBuilder::from(order)
+///     .time_in_force(TimeInForce::FillOrKill)
+///     .build();
+///
+/// assert_eq!(order.price.as_deref(), Some("30000"));
+/// ```
 #[derive(Debug)]
+pub enum TypedOrder {
+    /// Buy the base asset for `amount_quote` of the quote asset, at the market price.
+    MarketBuy {
+        market: String,
+        amount_quote: String,
+    },
+    /// Sell `amount` of the base asset, at the market price.
+    MarketSell { market: String, amount: String },
+    /// Trade `amount` of the base asset at `price` or better.
+    Limit {
+        market: String,
+        side: TradeSide,
+        amount: String,
+        price: String,
+    },
+    /// Trade at the market price once the trigger is reached.
+    StopLoss {
+        market: String,
+        side: TradeSide,
+        amount: OrderAmount,
+        trigger: Trigger,
+    },
+    /// Place a limit order once the trigger is reached.
+    StopLossLimit {
+        market: String,
+        side: TradeSide,
+        amount: String,
+        price: String,
+        trigger: Trigger,
+    },
+    /// Trade at the market price once the trigger is reached.
+    TakeProfit {
+        market: String,
+        side: TradeSide,
+        amount: OrderAmount,
+        trigger: Trigger,
+    },
+    /// Place a limit order once the trigger is reached.
+    TakeProfitLimit {
+        market: String,
+        side: TradeSide,
+        amount: String,
+        price: String,
+        trigger: Trigger,
+    },
+}
+
+/// The amount of an order trading at the market price, in either of the assets of the market.
+#[derive(Debug)]
+pub enum OrderAmount {
+    /// An amount of the base asset, set as `amount`.
+    Base(String),
+    /// An amount of the quote asset, set as `amountQuote`.
+    Quote(String),
+}
+
+/// The condition under which a stop loss or take profit order is placed.
+#[derive(Debug)]
+pub struct Trigger {
+    /// The price at which the order is triggered.
+    pub amount: String,
+    pub trigger_type: TriggerType,
+    pub reference: TriggerReference,
+}
+
+impl From<TypedOrder> for OrderBuilder {
+    fn from(order: TypedOrder) -> Self {
+        let with_amount = |builder: Self, amount| match amount {
+            OrderAmount::Base(amount) => builder.amount(amount),
+            OrderAmount::Quote(amount_quote) => builder.amount_quote(amount_quote),
+        };
+        let with_trigger = |builder: Self, trigger: Trigger| {
+            builder
+                .trigger_amount(trigger.amount)
+                .trigger_type(trigger.trigger_type)
+                .trigger_reference(trigger.reference)
+        };
+
+        match order {
+            TypedOrder::MarketBuy {
+                market,
+                amount_quote,
+            } => OrderBuilder::market_buy(market, amount_quote),
+            TypedOrder::MarketSell { market, amount } => OrderBuilder::market_sell(market, amount),
+            TypedOrder::Limit {
+                market,
+                side,
+                amount,
+                price,
+            } => OrderBuilder::new(market, side, OrderType::Limit)
+                .amount(amount)
+                .price(price),
+            TypedOrder::StopLoss {
+                market,
+                side,
+                amount,
+                trigger,
+            } => {
+                let builder = OrderBuilder::new(market, side, OrderType::StopLoss);
+                with_trigger(with_amount(builder, amount), trigger)
+            }
+            TypedOrder::StopLossLimit {
+                market,
+                side,
+                amount,
+                price,
+                trigger,
+            } => {
+                let builder = OrderBuilder::new(market, side, OrderType::StopLossLimit)
+                    .amount(amount)
+                    .price(price);
+                with_trigger(builder, trigger)
+            }
+            TypedOrder::TakeProfit {
+                market,
+                side,
+                amount,
+                trigger,
+            } => {
+                let builder = OrderBuilder::new(market, side, OrderType::TakeProfit);
+                with_trigger(with_amount(builder, amount), trigger)
+            }
+            TypedOrder::TakeProfitLimit {
+                market,
+                side,
+                amount,
+                price,
+                trigger,
+            } => {
+                let builder = OrderBuilder::new(market, side, OrderType::TakeProfitLimit)
+                    .amount(amount)
+                    .price(price);
+                with_trigger(builder, trigger)
+            }
+        }
+    }
+}
+
+impl From<TypedOrder> for Order {
+    fn from(order: TypedOrder) -> Self {
+        OrderBuilder::from(order).build()
+    }
+}
+
+/// The type of order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
     Market,
     Limit,
@@ -484,18 +2250,67 @@ pub enum OrderType {
     TakeProfitLimit,
 }
 
+impl OrderType {
+    /// The string used for the order type by the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Market => "market",
+            OrderType::Limit => "limit",
+            OrderType::StopLoss => "stopLoss",
+            OrderType::StopLossLimit => "stopLossLimit",
+            OrderType::TakeProfit => "takeProfit",
+            OrderType::TakeProfitLimit => "takeProfitLimit",
+        }
+    }
+
+    /// Whether orders of this type rest at a price, and therefore require one.
+    pub fn is_limit_like(&self) -> bool {
+        matches!(
+            self,
+            OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit
+        )
+    }
+
+    /// Whether orders of this type only become active once triggered, and therefore require the
+    /// trigger fields.
+    pub fn requires_trigger(&self) -> bool {
+        matches!(
+            self,
+            OrderType::StopLoss
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfit
+                | OrderType::TakeProfitLimit
+        )
+    }
+}
+
 impl Serialize for OrderType {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        match self {
-            OrderType::Market => serializer.serialize_str("market"),
-            OrderType::Limit => serializer.serialize_str("limit"),
-            OrderType::StopLoss => serializer.serialize_str("stopLoss"),
-            OrderType::StopLossLimit => serializer.serialize_str("stopLossLimit"),
-            OrderType::TakeProfit => serializer.serialize_str("takeProfit"),
-            OrderType::TakeProfitLimit => serializer.serialize_str("takeProfitLimit"),
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderType {
+    fn deserialize<D>(deserializer: D) -> crate::Result<OrderType, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "market" => Ok(OrderType::Market),
+            "limit" => Ok(OrderType::Limit),
+            "stopLoss" => Ok(OrderType::StopLoss),
+            "stopLossLimit" => Ok(OrderType::StopLossLimit),
+            "takeProfit" => Ok(OrderType::TakeProfit),
+            "takeProfitLimit" => Ok(OrderType::TakeProfitLimit),
+            s => Err(D::Error::invalid_value(
+                Unexpected::Str(s),
+                &"[market, limit, stopLoss, stopLossLimit, takeProfit, takeProfitLimit]",
+            )),
         }
     }
 }
@@ -517,6 +2332,20 @@ impl Serialize for TriggerType {
     }
 }
 
+impl<'de> Deserialize<'de> for TriggerType {
+    fn deserialize<D>(deserializer: D) -> crate::Result<TriggerType, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "price" => Ok(TriggerType::Price),
+            s => Err(D::Error::invalid_value(Unexpected::Str(s), &"[price]")),
+        }
+    }
+}
+
 /// The price type that triggers an order to be filled.
 #[derive(Debug)]
 pub enum TriggerReference {
@@ -540,16 +2369,36 @@ impl Serialize for TriggerReference {
     }
 }
 
-/// How long an order should remain active.
-#[derive(Debug)]
-pub enum TimeInForce {
-    GoodTillCancelled,
-    FillOrKill,
-    ImmediateOrCancel,
-}
-
-impl Serialize for TimeInForce {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<'de> Deserialize<'de> for TriggerReference {
+    fn deserialize<D>(deserializer: D) -> crate::Result<TriggerReference, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "lastTrade" => Ok(TriggerReference::LastTrade),
+            "bestBid" => Ok(TriggerReference::BestBid),
+            "bestAsk" => Ok(TriggerReference::BestAsk),
+            "midPrice" => Ok(TriggerReference::MidPrice),
+            s => Err(D::Error::invalid_value(
+                Unexpected::Str(s),
+                &"[lastTrade, bestBid, bestAsk, midPrice]",
+            )),
+        }
+    }
+}
+
+/// How long an order should remain active.
+#[derive(Debug)]
+pub enum TimeInForce {
+    GoodTillCancelled,
+    FillOrKill,
+    ImmediateOrCancel,
+}
+
+impl Serialize for TimeInForce {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
@@ -561,6 +2410,25 @@ impl Serialize for TimeInForce {
     }
 }
 
+impl<'de> Deserialize<'de> for TimeInForce {
+    fn deserialize<D>(deserializer: D) -> crate::Result<TimeInForce, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "GTC" => Ok(TimeInForce::GoodTillCancelled),
+            "FOK" => Ok(TimeInForce::FillOrKill),
+            "IOC" => Ok(TimeInForce::ImmediateOrCancel),
+            s => Err(D::Error::invalid_value(
+                Unexpected::Str(s),
+                &"[GTC, FOK, IOC]",
+            )),
+        }
+    }
+}
+
 /// How to handle self trades.
 #[derive(Debug)]
 pub enum SelfTradePrevention {
@@ -586,12 +2454,1649 @@ impl Serialize for SelfTradePrevention {
     }
 }
 
+impl<'de> Deserialize<'de> for SelfTradePrevention {
+    fn deserialize<D>(deserializer: D) -> crate::Result<SelfTradePrevention, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "decrementAndCancel" => Ok(SelfTradePrevention::DecrementAndCancel),
+            "cancelBoth" => Ok(SelfTradePrevention::CancelBoth),
+            "cancelNewest" => Ok(SelfTradePrevention::CancelNewest),
+            "cancelOldest" => Ok(SelfTradePrevention::CancelOldest),
+            s => Err(D::Error::invalid_value(
+                Unexpected::Str(s),
+                &"[decrementAndCancel, cancelBoth, cancelNewest, cancelOldest]",
+            )),
+        }
+    }
+}
+
+/// The response to placing an order.
+///
+/// The status of the order, how much of it was filled, and its fills are only included when
+/// the order was placed with `response_required` set.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderResponse {
     pub market: String,
     pub order_id: Uuid,
+    #[serde(default, deserialize_with = "de_opt_uuid")]
     pub client_order_id: Option<Uuid>,
     pub created: u64,
     pub updated: u64,
+    pub status: Option<OrderState>,
+    pub filled_amount: Option<String>,
+    pub filled_amount_quote: Option<String>,
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+}
+
+impl OrderResponse {
+    /// Whether the order was filled completely.
+    pub fn is_filled(&self) -> bool {
+        self.status == Some(OrderState::Filled)
+    }
+
+    /// Whether some, but not all, of the order was filled. This is the case for an
+    /// immediate-or-cancel order that could only be partially filled.
+    pub fn is_partially_filled(&self) -> bool {
+        let filled = self.filled_amount.as_deref().is_some_and(is_nonzero);
+        filled && !self.is_filled()
+    }
+
+    /// Whether the order ended without being filled completely because of its time in force,
+    /// e.g. a fill-or-kill order that could not be filled at once.
+    pub fn is_expired(&self) -> bool {
+        matches!(
+            self.status,
+            Some(OrderState::Expired | OrderState::CanceledIOC | OrderState::CanceledFOK)
+        )
+    }
+}
+
+/// Whether an amount is anything other than zero.
+fn is_nonzero(amount: &str) -> bool {
+    amount.bytes().any(|b| matches!(b, b'1'..=b'9'))
+}
+
+/// The state of an order on the exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    New,
+    AwaitingTrigger,
+    Canceled,
+    CanceledAuction,
+    CanceledSelfTradePrevention,
+    CanceledIOC,
+    CanceledFOK,
+    CanceledMarketProtection,
+    CanceledPostOnly,
+    Filled,
+    PartiallyFilled,
+    Expired,
+    Rejected,
+}
+
+impl OrderState {
+    /// Whether the order is done, and its state will not change anymore. Orders that are new,
+    /// awaiting their trigger, or partially filled are still open.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(
+            self,
+            OrderState::New | OrderState::AwaitingTrigger | OrderState::PartiallyFilled
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderState {
+    fn deserialize<D>(deserializer: D) -> crate::Result<OrderState, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        match s.as_str() {
+            "new" => Ok(OrderState::New),
+            "awaitingTrigger" => Ok(OrderState::AwaitingTrigger),
+            "canceled" => Ok(OrderState::Canceled),
+            "canceledAuction" => Ok(OrderState::CanceledAuction),
+            "canceledSelfTradePrevention" => Ok(OrderState::CanceledSelfTradePrevention),
+            "canceledIOC" => Ok(OrderState::CanceledIOC),
+            "canceledFOK" => Ok(OrderState::CanceledFOK),
+            "canceledMarketProtection" => Ok(OrderState::CanceledMarketProtection),
+            "canceledPostOnly" => Ok(OrderState::CanceledPostOnly),
+            "filled" => Ok(OrderState::Filled),
+            "partiallyFilled" => Ok(OrderState::PartiallyFilled),
+            "expired" => Ok(OrderState::Expired),
+            "rejected" => Ok(OrderState::Rejected),
+            s => Err(D::Error::invalid_value(
+                Unexpected::Str(s),
+                &"[new, awaitingTrigger, canceled, canceledAuction, canceledSelfTradePrevention, canceledIOC, canceledFOK, canceledMarketProtection, canceledPostOnly, filled, partiallyFilled, expired, rejected]",
+            )),
+        }
+    }
+}
+
+/// The full status of an order, including the fills it has received so far.
+///
+/// The amount of balance an order reserves, `on_hold` and `on_hold_currency`, is only reported
+/// while the order is still open, by both [`Client::get_order`] and [`Client::orders_open`].
+///
+/// [`Client::get_order`]: crate::Client::get_order
+/// [`Client::orders_open`]: crate::Client::orders_open
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderStatus {
+    pub order_id: Uuid,
+    #[serde(default, deserialize_with = "de_opt_uuid")]
+    pub client_order_id: Option<Uuid>,
+    pub market: String,
+    pub created: u64,
+    pub updated: u64,
+    pub status: OrderState,
+    pub side: TradeSide,
+    pub order_type: OrderType,
+    pub amount: Option<String>,
+    pub amount_remaining: Option<String>,
+    pub amount_quote: Option<String>,
+    pub amount_quote_remaining: Option<String>,
+    pub price: Option<String>,
+    /// The amount of balance reserved by the order.
+    pub on_hold: Option<String>,
+    /// The asset the reserved balance is in.
+    pub on_hold_currency: Option<String>,
+    pub filled_amount: String,
+    pub filled_amount_quote: String,
+    pub fee_paid: String,
+    pub fee_currency: Option<String>,
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+}
+
+impl OrderStatus {
+    /// Whether the order is done, as decided by [`OrderState::is_terminal`].
+    pub fn is_terminal(&self) -> bool {
+        self.status.is_terminal()
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl OrderStatus {
+    /// The amount of balance reserved by the order, in `on_hold_currency`.
+    ///
+    /// Returns `None` if the amount is not reported, or cannot be parsed.
+    pub fn on_hold_amount(&self) -> Option<Decimal> {
+        parse_decimal(self.on_hold.as_deref()?)
+    }
+}
+
+/// A fill of an order, either as part of an [`OrderStatus`] or as returned by
+/// [`Client::my_trades`].
+///
+/// The order id, market and side are only reported by [`Client::my_trades`], since for the fills
+/// of an order they are already known from the order itself. The fee and its currency are only
+/// known once the fill is settled.
+///
+/// [`Client::my_trades`]: crate::Client::my_trades
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    pub id: String,
+    pub order_id: Option<Uuid>,
+    pub market: Option<String>,
+    pub side: Option<TradeSide>,
+    pub timestamp: u64,
+    pub amount: String,
+    pub price: String,
+    pub taker: bool,
+    pub fee: Option<String>,
+    pub fee_currency: Option<String>,
+    pub settled: bool,
+}
+
+/// The outcome of cancelling several orders, as returned by [`Client::cancel_orders`].
+///
+/// Each order is either cancelled or failed, so orders that were already filled or gone can be
+/// told apart from those that were cancelled.
+///
+/// [`Client::cancel_orders`]: crate::Client::cancel_orders
+#[derive(Debug, Default)]
+pub struct BulkCancelResult {
+    pub cancelled: Vec<Uuid>,
+    pub failed: Vec<(Uuid, crate::Error)>,
+}
+
+/// The kind of a [`Transaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    Deposit,
+    Withdrawal,
+    Trade,
+}
+
+/// An event in the history of an account, as returned by [`Client::transaction_history`].
+///
+/// Fees are reported as part of each event.
+///
+/// [`Client::transaction_history`]: crate::Client::transaction_history
+#[derive(Debug)]
+pub enum Transaction {
+    Deposit(Deposit),
+    Withdrawal(Withdrawal),
+    Trade(Fill),
+}
+
+impl Transaction {
+    /// The kind of the transaction.
+    pub fn kind(&self) -> TransactionKind {
+        match self {
+            Transaction::Deposit(_) => TransactionKind::Deposit,
+            Transaction::Withdrawal(_) => TransactionKind::Withdrawal,
+            Transaction::Trade(_) => TransactionKind::Trade,
+        }
+    }
+
+    /// The time at which the transaction took place, in milliseconds since the epoch.
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            Transaction::Deposit(deposit) => deposit.timestamp,
+            Transaction::Withdrawal(withdrawal) => withdrawal.timestamp,
+            Transaction::Trade(fill) => fill.timestamp,
+        }
+    }
+}
+
+/// Options for [`Client::transaction_history`].
+///
+/// [`Client::transaction_history`]: crate::Client::transaction_history
+#[derive(Debug, Default)]
+pub struct TransactionHistoryOptions {
+    /// Only include transactions from this time on, in milliseconds since the epoch.
+    pub start: Option<u64>,
+    /// Only include transactions up to this time, in milliseconds since the epoch.
+    pub end: Option<u64>,
+    /// The kinds of transactions to include. All kinds are included when empty.
+    pub kinds: Vec<TransactionKind>,
+    /// The markets to include trades for. Bitvavo only reports trades per market, so no trades
+    /// are included when empty.
+    pub markets: Vec<String>,
+}
+
+impl TransactionHistoryOptions {
+    #[cfg(feature = "client")]
+    pub(crate) fn includes(&self, kind: TransactionKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[cfg(feature = "decimal")]
+    use std::str::FromStr;
+
+    #[cfg(feature = "decimal")]
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[cfg(feature = "decimal")]
+    fn ticker_24h(open: Option<&str>, last: Option<&str>) -> Ticker24h {
+        let ticker = fixtures::ticker_24h(serde_json::json!({ "open": open, "last": last }));
+        serde_json::from_value(ticker).expect("Deserializing the ticker should succeed")
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn ticker_24h_price_change() {
+        let ticker = ticker_24h(Some("200"), Some("210"));
+        assert_eq!(ticker.price_change(), Some(dec("10")));
+        assert_eq!(ticker.price_change_percent(), Some(dec("5")));
+
+        let ticker = ticker_24h(Some("200"), Some("150"));
+        assert_eq!(ticker.price_change(), Some(dec("-50")));
+        assert_eq!(ticker.price_change_percent(), Some(dec("-25")));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn fee_for_maker_and_taker() {
+        let fees: Fees = serde_json::from_str(
+            r#"{"tier": 0, "volume": "0.00", "taker": "0.0025", "maker": "0.0015"}"#,
+        )
+        .expect("Deserializing the fees should succeed");
+
+        assert_eq!(fees.fee_for(dec("1000"), true), Some(dec("1.5")));
+        assert_eq!(fees.fee_for(dec("1000"), false), Some(dec("2.5")));
+        assert_eq!(fees.fee_for(Decimal::ZERO, false), Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn candle_gaps() {
+        const HOUR: u64 = 3600 * 1000;
+        let series = |hours: &[u64]| -> Vec<OHLCV> {
+            let candles: Vec<_> = hours
+                .iter()
+                .map(|h| serde_json::json!([h * HOUR, "1", "1", "1", "1", "0.5"]))
+                .collect();
+            serde_json::from_value(serde_json::Value::Array(candles)).unwrap()
+        };
+
+        // Newest first, as returned by the API, missing hours 3, 6 and 7.
+        let candles = series(&[9, 8, 5, 4, 2, 1, 0]);
+        assert_eq!(
+            detect_gaps(&candles, CandleInterval::OneHour),
+            [(3 * HOUR, 4 * HOUR), (6 * HOUR, 8 * HOUR)]
+        );
+
+        // The same series in two hour candles has none.
+        let candles = series(&[8, 6, 4, 2, 0]);
+        assert!(detect_gaps(&candles, CandleInterval::TwoHours).is_empty());
+
+        assert!(detect_gaps(&series(&[]), CandleInterval::OneHour).is_empty());
+        assert!(detect_gaps(&series(&[5]), CandleInterval::OneHour).is_empty());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn ohlcv_to_numeric() {
+        let candle: OHLCV = serde_json::from_str(
+            r#"[1548684000000, "3212.1", "3258.4", "3198.9", "3242.2", "105.78962845"]"#,
+        )
+        .expect("Deserializing the candle should succeed");
+
+        let candle = candle
+            .to_numeric()
+            .expect("Converting the candle should succeed");
+        assert_eq!(
+            candle,
+            NumericCandle {
+                time: 1548684000000,
+                open: dec("3212.1"),
+                high: dec("3258.4"),
+                low: dec("3198.9"),
+                close: dec("3242.2"),
+                volume: dec("105.78962845"),
+            }
+        );
+
+        let candle: OHLCV = serde_json::from_str(
+            r#"[1548684000000, "3212.1", "3258.4", "3198.9", "NaN", "105.78962845"]"#,
+        )
+        .expect("Deserializing the candle should succeed");
+
+        let err = candle
+            .to_numeric()
+            .expect_err("Converting the candle should fail");
+        assert!(matches!(
+            err,
+            crate::Error::InvalidNumber { field: "close", value } if value == "NaN"
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn non_finite_numeric_strings() {
+        let candles: Vec<OHLCV> = serde_json::from_str(
+            r#"[
+                [1548684000000, "NaN", "Infinity", "", "-Infinity", "1.5"],
+                [1548680400000, "3000", "3100", "2900", "3050", "12.5"]
+            ]"#,
+        )
+        .expect("Deserializing the candles should succeed");
+        assert_eq!(candles[0].open, "NaN");
+        assert_eq!(candles[0].low, "");
+        assert_eq!(parse_decimal(&candles[0].open), None);
+        assert_eq!(parse_decimal(&candles[0].high), None);
+        assert_eq!(parse_decimal(&candles[0].low), None);
+        assert_eq!(parse_decimal(&candles[0].close), None);
+        assert_eq!(parse_decimal(&candles[1].close), Some(dec("3050")));
+
+        let tickers: Vec<Ticker24h> = serde_json::from_str(
+            r#"[
+                {"market": "BTC-EUR", "open": "NaN", "last": "31000"},
+                {"market": "ETH-EUR", "open": "2000", "last": ""},
+                {"market": "XRP-EUR", "open": "0.5", "last": "0.55"}
+            ]"#,
+        )
+        .expect("Deserializing the tickers should succeed");
+        assert_eq!(tickers[0].price_change(), None);
+        assert_eq!(tickers[1].price_change(), None);
+        assert_eq!(tickers[2].price_change(), Some(dec("0.05")));
+
+        let balances: Vec<Balance> = serde_json::from_str(
+            r#"[
+                {"symbol": "BTC", "available": "Infinity", "inOrder": "0"},
+                {"symbol": "ETH", "available": "1", "inOrder": ""},
+                {"symbol": "EUR", "available": "100", "inOrder": "50"}
+            ]"#,
+        )
+        .expect("Deserializing the balances should succeed");
+        assert_eq!(balances[0].total(), None);
+        assert_eq!(balances[1].total(), None);
+        assert_eq!(balances[2].total(), Some(dec("150")));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn ticker_24h_price_change_missing_or_zero_open() {
+        let ticker = ticker_24h(None, Some("210"));
+        assert_eq!(ticker.price_change(), None);
+        assert_eq!(ticker.price_change_percent(), None);
+
+        let ticker = ticker_24h(Some("200"), None);
+        assert_eq!(ticker.price_change(), None);
+        assert_eq!(ticker.price_change_percent(), None);
+
+        let ticker = ticker_24h(Some("0"), Some("210"));
+        assert_eq!(ticker.price_change(), Some(dec("210")));
+        assert_eq!(ticker.price_change_percent(), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn ticker_24h_spread() {
+        let ticker = |bid: Option<&str>, ask: Option<&str>| -> Ticker24h {
+            let ticker = fixtures::ticker_24h(serde_json::json!({ "bid": bid, "ask": ask }));
+            serde_json::from_value(ticker).expect("Deserializing the ticker should succeed")
+        };
+
+        let full = ticker(Some("99.5"), Some("100.5"));
+        assert_eq!(full.spread(), Some(dec("1")));
+        assert_eq!(full.mid(), Some(dec("100")));
+        assert_eq!(full.spread_bps(), Some(dec("100")));
+
+        for partial in [
+            ticker(Some("99.5"), None),
+            ticker(None, Some("100.5")),
+            ticker(None, None),
+        ] {
+            assert_eq!(partial.spread(), None);
+            assert_eq!(partial.mid(), None);
+            assert_eq!(partial.spread_bps(), None);
+        }
+
+        let zero = ticker(Some("0"), Some("0"));
+        assert_eq!(zero.spread(), Some(dec("0")));
+        assert_eq!(zero.spread_bps(), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn balance_total() {
+        let balance: Balance =
+            serde_json::from_str(r#"{"symbol":"BTC","available":"1.5","inOrder":"0.25"}"#)
+                .expect("Deserializing the balance should succeed");
+
+        assert_eq!(balance.total(), Some(dec("1.75")));
+    }
+
+    #[cfg(feature = "decimal")]
+    fn btc_eur_market() -> Market {
+        serde_json::from_value(fixtures::market(serde_json::json!({})))
+            .expect("Deserializing the market should succeed")
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn base_quote_conversion() {
+        assert_eq!(
+            base_to_quote(dec("0.5"), dec("30000"), 2),
+            Some(dec("15000"))
+        );
+        assert_eq!(
+            quote_to_base(dec("15000"), dec("30000"), 8),
+            Some(dec("0.5"))
+        );
+        assert_eq!(quote_to_base(dec("100"), Decimal::ZERO, 8), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn conversion_truncates_at_decimals() {
+        // Amounts are limited by the decimals of their asset, not the price precision.
+        assert_eq!(
+            base_to_quote(dec("1.5"), dec("12345.67"), 2),
+            Some(dec("18518.50"))
+        );
+        // The next decimal is truncated, never rounded up.
+        assert_eq!(
+            base_to_quote(dec("0.123459"), dec("1"), 5),
+            Some(dec("0.12345"))
+        );
+        assert_eq!(
+            quote_to_base(dec("10"), dec("3"), 8),
+            Some(dec("3.33333333"))
+        );
+        assert_eq!(quote_to_base(dec("10"), dec("3"), 0), Some(dec("3")));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn market_price_ladder() {
+        let market = btc_eur_market();
+
+        let ladder = |from, to, steps| market.price_ladder(dec(from), dec(to), steps);
+
+        assert_eq!(
+            ladder("100", "101", 3).unwrap(),
+            [dec("100"), dec("100.5"), dec("101")]
+        );
+        assert_eq!(ladder("100", "101", 1).unwrap(), [dec("100")]);
+        assert_eq!(ladder("100", "100", 3).unwrap(), [dec("100")]);
+        assert!(ladder("100", "101", 0).unwrap().is_empty());
+
+        // Past 10, one decimal fewer fits in 5 significant digits: 10.0002 and 10.0006 round to
+        // 10.000 and 10.001, the latter of which is also the last price.
+        assert_eq!(
+            ladder("9.9998", "10.001", 4).unwrap(),
+            [dec("9.9998"), dec("10.000"), dec("10.001")]
+        );
+        // Prices closer together than the precision collapse into one.
+        assert_eq!(
+            ladder("10.000", "10.001", 5).unwrap(),
+            [dec("10.000"), dec("10.001")]
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn market_price_ladder_descending() {
+        let market = btc_eur_market();
+
+        let ladder = |from, to, steps| market.price_ladder(dec(from), dec(to), steps);
+
+        assert_eq!(
+            ladder("101", "100", 3).unwrap(),
+            [dec("101"), dec("100.5"), dec("100")]
+        );
+        // The step of -1/3 does not add up to `to` exactly, but the last price is pinned to it.
+        assert_eq!(
+            ladder("101", "100", 4).unwrap(),
+            [dec("101"), dec("100.67"), dec("100.33"), dec("100")]
+        );
+        assert_eq!(
+            ladder("10.001", "9.9998", 4).unwrap(),
+            [dec("10.001"), dec("10.000"), dec("9.9998")]
+        );
+
+        let err = market
+            .price_ladder(Decimal::MIN, Decimal::MAX, 3)
+            .expect_err("An overflowing ladder should fail");
+        assert!(matches!(
+            err,
+            crate::Error::InvalidNumber { field: "to", .. }
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn format_amounts() {
+        let cases = [
+            ("1.5", 0, RoundingMode::ToZero, "1"),
+            ("1.5", 0, RoundingMode::NearestEven, "2"),
+            ("-1.5", 0, RoundingMode::ToZero, "-1"),
+            ("0.123456789", 8, RoundingMode::ToZero, "0.12345678"),
+            ("0.123456789", 8, RoundingMode::AwayFromZero, "0.12345679"),
+            ("12", 8, RoundingMode::ToZero, "12.00000000"),
+            ("0.000000001", 8, RoundingMode::ToZero, "0.00000000"),
+            ("1", 18, RoundingMode::ToZero, "1.000000000000000000"),
+            (
+                "0.1",
+                28,
+                RoundingMode::ToZero,
+                "0.1000000000000000000000000000",
+            ),
+            (
+                "0.1",
+                40,
+                RoundingMode::ToZero,
+                "0.1000000000000000000000000000",
+            ),
+        ];
+        for (amount, decimals, mode, expected) in cases {
+            assert_eq!(
+                format_amount(dec(amount), decimals, mode),
+                expected,
+                "{amount} with {decimals} decimals, rounded {mode:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn rounding_modes() {
+        let market = btc_eur_market();
+        let amount_rounding = |amount| Rounding {
+            amount,
+            ..Rounding::default()
+        };
+
+        let cases = [
+            (RoundingMode::ToZero, ["1.2346", "1.2346", "1.2345"]),
+            (RoundingMode::AwayFromZero, ["1.2347", "1.2347", "1.2346"]),
+            (
+                RoundingMode::NearestAwayFromZero,
+                ["1.2346", "1.2347", "1.2346"],
+            ),
+            (RoundingMode::NearestEven, ["1.2346", "1.2346", "1.2346"]),
+        ];
+        for (mode, expected) in cases {
+            // Just past a boundary, and at the midpoints above an even and an odd digit.
+            for (value, expected) in ["1.23461", "1.23465", "1.23455"].into_iter().zip(expected) {
+                assert_eq!(
+                    base_to_quote_with(dec("1"), dec(value), 4, amount_rounding(mode)),
+                    Some(dec(expected)),
+                    "{value} rounded {mode:?}"
+                );
+            }
+        }
+
+        // The defaults truncate amounts, and round prices to the nearest value.
+        assert_eq!(
+            base_to_quote(dec("1"), dec("1.23465"), 4),
+            Some(dec("1.2346"))
+        );
+        assert_eq!(
+            market
+                .price_ladder(dec("1.23445"), dec("1.23455"), 2)
+                .unwrap(),
+            [dec("1.2345"), dec("1.2346")]
+        );
+
+        let rounding = Rounding {
+            price: RoundingMode::NearestEven,
+            ..Rounding::default()
+        };
+        assert_eq!(
+            market
+                .price_ladder_with(dec("1.23445"), dec("1.23455"), 2, rounding)
+                .unwrap(),
+            [dec("1.2344"), dec("1.2346")]
+        );
+
+        let fees = Fees {
+            tier: 0,
+            volume: String::from("0"),
+            taker: String::from("0.0025"),
+            maker: String::from("0.0015"),
+        };
+        let rounding = amount_rounding(RoundingMode::AwayFromZero);
+        let order = OrderBuilder::spend_inclusive_with("BTC-EUR", dec("100"), 2, &fees, rounding)
+            .unwrap()
+            .build();
+        assert_eq!(order.amount_quote.as_deref(), Some("99.76"));
+    }
+
+    #[test]
+    fn market_diff() {
+        let market = |pair: &str, price_precision: u64| -> Market {
+            let market = fixtures::market(serde_json::json!({
+                "market": pair,
+                "pricePrecision": price_precision,
+            }));
+            serde_json::from_value(market).expect("Deserializing the market should succeed")
+        };
+
+        let previous = [
+            market("BTC-EUR", 5),
+            market("ETH-EUR", 5),
+            market("OLD-EUR", 5),
+        ];
+        let current = [
+            market("BTC-EUR", 5),
+            market("ETH-EUR", 6),
+            market("NEW-EUR", 5),
+        ];
+
+        let diff = MarketDiff::between(&previous, &current);
+        assert!(!diff.is_empty());
+
+        let pairs = |markets: &[Market]| markets.iter().map(|m| m.pair.clone()).collect::<Vec<_>>();
+        assert_eq!(pairs(&diff.added), ["NEW-EUR"]);
+        assert_eq!(pairs(&diff.removed), ["OLD-EUR"]);
+
+        assert_eq!(diff.changed.len(), 1);
+        let (before, after) = &diff.changed[0];
+        assert_eq!(before.pair, "ETH-EUR");
+        assert_eq!((before.price_precision, after.price_precision), (5, 6));
+
+        assert!(MarketDiff::between(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn fees_as_strings_or_numbers() {
+        let fees: Fees = serde_json::from_str(
+            r#"{"tier":0,"volume":"10000.00","taker":"0.0025","maker":"0.0015"}"#,
+        )
+        .expect("Deserializing fees as strings should succeed");
+        assert_eq!(fees.volume, "10000.00");
+        assert_eq!(fees.taker, "0.0025");
+
+        let fees: Fees =
+            serde_json::from_str(r#"{"tier":0,"volume":10000,"taker":0.0025,"maker":0.0015}"#)
+                .expect("Deserializing fees as numbers should succeed");
+        assert_eq!(fees.volume, "10000");
+        assert_eq!(fees.taker, "0.0025");
+        assert_eq!(fees.maker, "0.0015");
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn fees_as_numbers_to_decimal() {
+        let fees: Fees = serde_json::from_str(
+            r#"{"tier":0,"volume":1.5e4,"taker":0.30000000000000004,"maker":0.0015}"#,
+        )
+        .expect("Deserializing fees as numbers should succeed");
+        assert_eq!(fees.volume, "15000");
+        assert_eq!(fees.taker, "0.3");
+        assert_eq!(parse_decimal(&fees.maker), Some(dec("0.0015")));
+    }
+
+    #[test]
+    fn ticker_prices_as_strings_or_numbers() {
+        let tickers: Vec<TickerPrice> = serde_json::from_str(
+            r#"[
+                {"market":"BTC-EUR","price":"30000.5"},
+                {"market":"ETH-EUR","price":2000.25},
+                {"market":"NEW-EUR","price":null},
+                {"market":"OLD-EUR"}
+            ]"#,
+        )
+        .expect("Deserializing the ticker prices should succeed");
+
+        assert_eq!(tickers[0].price.as_deref(), Some("30000.5"));
+        assert_eq!(tickers[1].price.as_deref(), Some("2000.25"));
+        assert_eq!(tickers[2].price, None);
+        assert_eq!(tickers[3].price, None);
+
+        let ticker: Ticker24h =
+            serde_json::from_str(r#"{"market":"BTC-EUR","open":30000,"last":"31000","bid":null}"#)
+                .expect("Deserializing the 24h ticker should succeed");
+        assert_eq!(ticker.open.as_deref(), Some("30000"));
+        assert_eq!(ticker.last.as_deref(), Some("31000"));
+        assert_eq!(ticker.bid, None);
+    }
+
+    fn order_response(status: &str, filled_amount: &str) -> OrderResponse {
+        let order = fixtures::order(serde_json::json!({
+            "status": status,
+            "amount": "1",
+            "price": "30000",
+            "filledAmount": filled_amount,
+            "timeInForce": "IOC",
+        }));
+        serde_json::from_value(order).expect("Deserializing the order response should succeed")
+    }
+
+    #[test]
+    fn order_response_outcomes() {
+        let filled = order_response("filled", "1");
+        assert!(filled.is_filled());
+        assert!(!filled.is_partially_filled());
+        assert!(!filled.is_expired());
+
+        let ioc_partial = order_response("canceledIOC", "0.4");
+        assert!(!ioc_partial.is_filled());
+        assert!(ioc_partial.is_partially_filled());
+        assert!(ioc_partial.is_expired());
+
+        let fok_killed = order_response("canceledFOK", "0");
+        assert!(!fok_killed.is_filled());
+        assert!(!fok_killed.is_partially_filled());
+        assert!(fok_killed.is_expired());
+
+        let without_client_id: OrderResponse = serde_json::from_str(
+            r#"{
+                "orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "clientOrderId": "",
+                "market": "BTC-EUR",
+                "created": 1542621155181,
+                "updated": 1542621155181
+            }"#,
+        )
+        .expect("Deserializing the order response should succeed");
+        assert_eq!(without_client_id.client_order_id, None);
+
+        let with_client_id: OrderResponse = serde_json::from_str(
+            r#"{
+                "orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "clientOrderId": "2be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "market": "BTC-EUR",
+                "created": 1542621155181,
+                "updated": 1542621155181
+            }"#,
+        )
+        .expect("Deserializing the order response should succeed");
+        assert!(with_client_id.client_order_id.is_some());
+
+        let minimal: OrderResponse = serde_json::from_str(
+            r#"{
+                "orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "market": "BTC-EUR",
+                "created": 1542621155181,
+                "updated": 1542621155181
+            }"#,
+        )
+        .expect("Deserializing the order response should succeed");
+        assert!(!minimal.is_filled());
+        assert!(!minimal.is_partially_filled());
+        assert!(!minimal.is_expired());
+    }
+
+    #[test]
+    fn order_status_with_fills() {
+        let order: OrderStatus = serde_json::from_str(
+            r#"{
+                "orderId": "1be6d0df-d5dc-4b53-a250-3376f3b393e6",
+                "market": "BTC-EUR",
+                "created": 1542621155181,
+                "updated": 1542621155181,
+                "status": "partiallyFilled",
+                "side": "buy",
+                "orderType": "limit",
+                "amount": "0.5",
+                "amountRemaining": "0.25",
+                "price": "30000",
+                "onHold": "7518.75",
+                "onHoldCurrency": "EUR",
+                "filledAmount": "0.25",
+                "filledAmountQuote": "7500",
+                "feePaid": "18.75",
+                "feeCurrency": "EUR",
+                "fills": [
+                    {
+                        "id": "371c6bd3-d06d-4573-9f15-18697cd210e5",
+                        "timestamp": 1542967486256,
+                        "amount": "0.25",
+                        "price": "30000",
+                        "taker": true,
+                        "fee": "18.75",
+                        "feeCurrency": "EUR",
+                        "settled": true
+                    }
+                ],
+                "selfTradePrevention": "decrementAndCancel",
+                "visible": true,
+                "timeInForce": "GTC",
+                "postOnly": false
+            }"#,
+        )
+        .expect("Deserializing the order status should succeed");
+
+        assert_eq!(order.status, OrderState::PartiallyFilled);
+        assert_eq!(order.on_hold.as_deref(), Some("7518.75"));
+        assert_eq!(order.on_hold_currency.as_deref(), Some("EUR"));
+        #[cfg(feature = "decimal")]
+        assert_eq!(order.on_hold_amount(), Some(dec("7518.75")));
+        assert_eq!(order.fills.len(), 1);
+
+        let fill = &order.fills[0];
+        assert!(fill.taker);
+        assert!(fill.settled);
+        assert!(fill.side.is_none());
+        assert_eq!(fill.fee.as_deref(), Some("18.75"));
+        assert_eq!(fill.fee_currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn my_trades_fills() {
+        let fills: Vec<Fill> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "108c3633-0276-4480-a902-17a01829deae",
+                    "orderId": "1d671998-3d44-4df4-965f-0d48bd129a1b",
+                    "timestamp": 1542967486256,
+                    "market": "BTC-EUR",
+                    "side": "sell",
+                    "amount": "0.005",
+                    "price": "5000.1",
+                    "taker": false,
+                    "fee": "0.03",
+                    "feeCurrency": "EUR",
+                    "settled": true
+                },
+                {
+                    "id": "2d5a8b0a-6a8b-4f4e-9e55-2f1ab8e4a1c3",
+                    "orderId": "1d671998-3d44-4df4-965f-0d48bd129a1b",
+                    "timestamp": 1542967486257,
+                    "market": "BTC-EUR",
+                    "side": "sell",
+                    "amount": "0.001",
+                    "price": "5000.1",
+                    "taker": true,
+                    "settled": false
+                }
+            ]"#,
+        )
+        .expect("Deserializing the fills should succeed");
+
+        assert!(matches!(fills[0].side, Some(TradeSide::Sell)));
+        assert_eq!(fills[0].market.as_deref(), Some("BTC-EUR"));
+        assert!(!fills[0].taker);
+        assert!(fills[1].taker);
+        assert!(!fills[1].settled);
+        assert!(fills[1].fee.is_none());
+    }
+
+    fn trade(id: &str, timestamp: u64) -> Trade {
+        Trade {
+            id: id.to_string(),
+            timestamp,
+            amount: String::from("0.1"),
+            price: String::from("30000"),
+            side: TradeSide::Sell,
+        }
+    }
+
+    #[test]
+    fn trade_ordering() {
+        assert!(trade("b", 1) < trade("a", 2));
+        assert!(trade("a", 1) < trade("b", 1));
+        assert_eq!(trade("a", 1), trade("a", 1));
+    }
+
+    #[test]
+    fn merge_overlapping_trade_pages() {
+        // Pages are newest first, and share trades at their boundaries.
+        let pages = [
+            vec![trade("f", 30), trade("e", 20), trade("d", 20)],
+            vec![trade("e", 20), trade("d", 20), trade("c", 10)],
+            vec![trade("c", 10), trade("b", 10), trade("a", 5)],
+        ];
+
+        let trades = Trade::merge_sorted(pages);
+
+        let ids: Vec<_> = trades.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c", "d", "e", "f"]);
+    }
+
+    #[test]
+    fn validate_market_order_amounts() {
+        use crate::{Error, InvalidOrder};
+
+        let market = |side| OrderBuilder::new("BTC-EUR", side, OrderType::Market);
+
+        assert!(OrderBuilder::market_buy("BTC-EUR", "100")
+            .build()
+            .validate()
+            .is_ok());
+        assert!(OrderBuilder::market_sell("BTC-EUR", "0.1")
+            .build()
+            .validate()
+            .is_ok());
+
+        let order = market(TradeSide::Buy).amount("0.1").build();
+        assert!(matches!(
+            order.validate(),
+            Err(Error::InvalidOrder(InvalidOrder::AmountOnMarketBuy))
+        ));
+
+        let order = market(TradeSide::Sell).amount_quote("100").build();
+        assert!(matches!(
+            order.validate(),
+            Err(Error::InvalidOrder(InvalidOrder::AmountQuoteOnMarketSell))
+        ));
+
+        let order = market(TradeSide::Buy)
+            .amount("0.1")
+            .amount_quote("100")
+            .build();
+        assert!(matches!(
+            order.validate(),
+            Err(Error::InvalidOrder(InvalidOrder::BothAmounts))
+        ));
+
+        let order = market(TradeSide::Sell).build();
+        assert!(matches!(
+            order.validate(),
+            Err(Error::InvalidOrder(InvalidOrder::MissingAmount))
+        ));
+
+        // Only market orders are checked.
+        let order = OrderBuilder::new("BTC-EUR", TradeSide::Buy, OrderType::Limit)
+            .amount("0.1")
+            .price("30000")
+            .build();
+        assert!(order.validate().is_ok());
+    }
+
+    #[test]
+    fn deposit_info_crypto_and_fiat() {
+        let info: DepositInfo = serde_json::from_str(
+            r#"{"address": "rPEPPER7kfTD9w2To4CQk6UCfuHM9c6GDY", "paymentId": "10002653"}"#,
+        )
+        .expect("Deserializing the crypto deposit info should succeed");
+        assert!(matches!(
+            info,
+            DepositInfo::Crypto(CryptoDepositInfo { payment_id: Some(id), .. }) if id == "10002653"
+        ));
+
+        let info: DepositInfo = serde_json::from_str(
+            r#"{"iban": "NL32BUNQ2291234129", "bic": "BUNQNL2A", "description": "254D20CC94"}"#,
+        )
+        .expect("Deserializing the fiat deposit info should succeed");
+        assert!(matches!(
+            info,
+            DepositInfo::Fiat(FiatDepositInfo { description, .. }) if description == "254D20CC94"
+        ));
+    }
+
+    #[test]
+    fn trade_side_wire_strings() {
+        for side in [TradeSide::Buy, TradeSide::Sell] {
+            let json = serde_json::to_value(side).expect("Serializing should succeed");
+            assert_eq!(json, side.as_query_str());
+
+            let parsed: TradeSide =
+                serde_json::from_value(json).expect("Deserializing should succeed");
+            assert_eq!(parsed, side);
+        }
+    }
+
+    #[test]
+    fn candle_interval_wire_strings() {
+        let interval = CandleInterval::OneHour;
+        assert_eq!(interval.as_str(), "1h");
+        assert_eq!(interval.to_string(), "1h");
+        assert_eq!(
+            serde_json::to_value(interval).expect("Serializing should succeed"),
+            "1h"
+        );
+    }
+
+    #[test]
+    fn timed_age() {
+        let timed = Timed {
+            value: (),
+            received_at: UNIX_EPOCH + Duration::from_millis(1_500),
+            server_time: Some(1_000),
+        };
+        assert_eq!(timed.age(), Some(Duration::from_millis(500)));
+
+        let timed = Timed {
+            server_time: Some(2_000),
+            ..timed
+        };
+        assert_eq!(timed.age(), Some(Duration::ZERO));
+
+        let timed = Timed {
+            server_time: None,
+            ..timed
+        };
+        assert_eq!(timed.age(), None);
+    }
+
+    #[test]
+    fn order_builder_defaults() {
+        let order = OrderBuilder::new("BTC-EUR", TradeSide::Sell, OrderType::Market)
+            .amount("0.5")
+            .build();
+
+        let json = serde_json::to_value(&order).expect("Serializing the order should succeed");
+        assert_eq!(json["market"], "BTC-EUR");
+        assert_eq!(json["side"], "sell");
+        assert_eq!(json["orderType"], "market");
+        assert_eq!(json["amount"], "0.5");
+        assert_eq!(json["responseRequired"], true);
+        assert_eq!(json["disableMarketProtection"], false);
+
+        let order = OrderBuilder::new("BTC-EUR", TradeSide::Sell, OrderType::Market)
+            .amount("0.5")
+            .response_required(false)
+            .build();
+        assert!(!order.response_required);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn spend_inclusive() {
+        let fees = |taker: &str| Fees {
+            tier: 0,
+            volume: String::from("0"),
+            taker: String::from(taker),
+            maker: String::from("0.0015"),
+        };
+
+        let order = OrderBuilder::spend_inclusive("BTC-EUR", dec("100"), 2, &fees("0.0025"))
+            .unwrap()
+            .build();
+        assert_eq!(order.side, TradeSide::Buy);
+        assert_eq!(order.amount_quote.as_deref(), Some("99.75"));
+
+        let amount_quote = dec(order.amount_quote.as_deref().unwrap());
+        assert!(amount_quote * dec("1.0025") <= dec("100"));
+
+        // The quote asset of ETH-BTC has 8 decimals.
+        let order = OrderBuilder::spend_inclusive("ETH-BTC", dec("1"), 8, &fees("0.0025"))
+            .unwrap()
+            .build();
+        assert_eq!(order.amount_quote.as_deref(), Some("0.99750623"));
+
+        assert!(OrderBuilder::spend_inclusive("BTC-EUR", dec("100"), 2, &fees("NaN")).is_none());
+    }
+
+    #[test]
+    fn order_round_trip() {
+        let order = OrderBuilder::new("BTC-EUR", TradeSide::Buy, OrderType::Limit)
+            .client_order_id(Uuid::nil())
+            .amount("0.1")
+            .price("30000")
+            .time_in_force(TimeInForce::ImmediateOrCancel)
+            .self_trade_prevention(SelfTradePrevention::CancelOldest)
+            .build();
+
+        let json = serde_json::to_value(&order).expect("Serializing the order should succeed");
+        let echoed: Order =
+            serde_json::from_value(json.clone()).expect("Deserializing the order should succeed");
+
+        assert_eq!(echoed.order_type, OrderType::Limit);
+        assert_eq!(echoed.price.as_deref(), Some("30000"));
+        assert_eq!(serde_json::to_value(&echoed).unwrap(), json);
+    }
+
+    #[test]
+    fn typed_order_serialization() {
+        let trigger = || Trigger {
+            amount: "29000".to_string(),
+            trigger_type: TriggerType::Price,
+            reference: TriggerReference::LastTrade,
+        };
+        let market = || "BTC-EUR".to_string();
+
+        let cases = [
+            (
+                TypedOrder::MarketBuy {
+                    market: market(),
+                    amount_quote: "100".to_string(),
+                },
+                serde_json::json!({"side": "buy", "orderType": "market", "amountQuote": "100"}),
+            ),
+            (
+                TypedOrder::MarketSell {
+                    market: market(),
+                    amount: "0.1".to_string(),
+                },
+                serde_json::json!({"side": "sell", "orderType": "market", "amount": "0.1"}),
+            ),
+            (
+                TypedOrder::Limit {
+                    market: market(),
+                    side: TradeSide::Buy,
+                    amount: "0.1".to_string(),
+                    price: "30000".to_string(),
+                },
+                serde_json::json!({
+                    "side": "buy", "orderType": "limit", "amount": "0.1", "price": "30000",
+                }),
+            ),
+            (
+                TypedOrder::StopLoss {
+                    market: market(),
+                    side: TradeSide::Sell,
+                    amount: OrderAmount::Base("0.1".to_string()),
+                    trigger: trigger(),
+                },
+                serde_json::json!({
+                    "side": "sell", "orderType": "stopLoss", "amount": "0.1",
+                    "triggerAmount": "29000", "triggerType": "price",
+                    "triggerReference": "lastTrade",
+                }),
+            ),
+            (
+                TypedOrder::StopLossLimit {
+                    market: market(),
+                    side: TradeSide::Sell,
+                    amount: "0.1".to_string(),
+                    price: "28900".to_string(),
+                    trigger: trigger(),
+                },
+                serde_json::json!({
+                    "side": "sell", "orderType": "stopLossLimit", "amount": "0.1",
+                    "price": "28900", "triggerAmount": "29000", "triggerType": "price",
+                    "triggerReference": "lastTrade",
+                }),
+            ),
+            (
+                TypedOrder::TakeProfit {
+                    market: market(),
+                    side: TradeSide::Buy,
+                    amount: OrderAmount::Quote("100".to_string()),
+                    trigger: trigger(),
+                },
+                serde_json::json!({
+                    "side": "buy", "orderType": "takeProfit", "amountQuote": "100",
+                    "triggerAmount": "29000", "triggerType": "price",
+                    "triggerReference": "lastTrade",
+                }),
+            ),
+            (
+                TypedOrder::TakeProfitLimit {
+                    market: market(),
+                    side: TradeSide::Sell,
+                    amount: "0.1".to_string(),
+                    price: "31000".to_string(),
+                    trigger: trigger(),
+                },
+                serde_json::json!({
+                    "side": "sell", "orderType": "takeProfitLimit", "amount": "0.1",
+                    "price": "31000", "triggerAmount": "29000", "triggerType": "price",
+                    "triggerReference": "lastTrade",
+                }),
+            ),
+        ];
+
+        for (order, expected) in cases {
+            let json = serde_json::to_value(Order::from(order))
+                .expect("Serializing the order should succeed");
+            assert_eq!(json["market"], "BTC-EUR");
+
+            // Every field set by the variant is serialized, and nothing else is.
+            let set: serde_json::Map<_, _> = json
+                .as_object()
+                .unwrap()
+                .iter()
+                .filter(|(key, value)| {
+                    !value.is_null()
+                        && !["market", "disableMarketProtection", "responseRequired"]
+                            .contains(&key.as_str())
+                })
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            assert_eq!(serde_json::Value::Object(set), expected);
+        }
+    }
+
+    #[test]
+    fn order_type_classification() {
+        let cases = [
+            (OrderType::Market, "market", false, false),
+            (OrderType::Limit, "limit", true, false),
+            (OrderType::StopLoss, "stopLoss", false, true),
+            (OrderType::StopLossLimit, "stopLossLimit", true, true),
+            (OrderType::TakeProfit, "takeProfit", false, true),
+            (OrderType::TakeProfitLimit, "takeProfitLimit", true, true),
+        ];
+
+        for (order_type, s, limit_like, trigger) in cases {
+            assert_eq!(order_type.as_str(), s);
+            assert_eq!(serde_json::to_value(order_type).unwrap(), s);
+            assert_eq!(order_type.is_limit_like(), limit_like, "{s}");
+            assert_eq!(order_type.requires_trigger(), trigger, "{s}");
+        }
+    }
+
+    #[test]
+    fn market_pair_parts() {
+        let pair = MarketPair::new(Symbol::from("BTC"), "EUR");
+        assert_eq!(pair.as_str(), "BTC-EUR");
+        assert_eq!(pair.base(), "BTC");
+        assert_eq!(pair.quote(), "EUR");
+        assert_eq!(pair, MarketPair::from("BTC-EUR"));
+
+        let odd = MarketPair::from("BTC");
+        assert_eq!(odd.base(), "BTC");
+        assert_eq!(odd.quote(), "");
+    }
+
+    #[test]
+    fn split_market_pairs() {
+        assert_eq!(split_market_pair("BTC-EUR"), Some(("BTC", "EUR")));
+        assert_eq!(split_market_pair("1INCH-EUR"), Some(("1INCH", "EUR")));
+
+        for malformed in ["", "BTC", "BTCEUR", "-EUR", "BTC-", "-", "BTC-EUR-X"] {
+            assert_eq!(split_market_pair(malformed), None, "{malformed:?}");
+        }
+    }
+
+    #[test]
+    fn terminal_order_states() {
+        let open = [
+            OrderState::New,
+            OrderState::AwaitingTrigger,
+            OrderState::PartiallyFilled,
+        ];
+        for state in open {
+            assert!(!state.is_terminal(), "{state:?}");
+        }
+
+        let done = [
+            OrderState::Filled,
+            OrderState::Canceled,
+            OrderState::CanceledIOC,
+            OrderState::CanceledPostOnly,
+            OrderState::Expired,
+            OrderState::Rejected,
+        ];
+        for state in done {
+            assert!(state.is_terminal(), "{state:?}");
+        }
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn order_book_depth() {
+        assert_eq!(Depth::Full.levels(), None);
+        assert_eq!(Depth::Levels(25).levels(), Some(25));
+        assert_eq!(Depth::Levels(5000).levels(), Some(Depth::MAX));
+
+        assert_eq!(Depth::from(None), Depth::Full);
+        assert_eq!(Depth::from(Some(25)), Depth::Levels(25));
+        assert_eq!(Depth::from(Some(u64::MAX)).levels(), Some(Depth::MAX));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn market_with_max_slippage() {
+        let market: Market =
+            serde_json::from_value(fixtures::market(serde_json::json!({ "market": "XRP-EUR" })))
+                .expect("Deserializing the market should succeed");
+        let book: OrderBook = serde_json::from_str(
+            r#"{"market":"XRP-EUR","nonce":1,"bids":[["0.51234","10"]],"asks":[["0.51244","10"]]}"#,
+        )
+        .expect("Deserializing the order book should succeed");
+
+        let order = |market: &Market, side, book: &OrderBook, max_slippage| {
+            OrderBuilder::market_with_max_slippage(market, side, dec("10"), book, dec(max_slippage))
+                .map(OrderBuilder::build)
+        };
+
+        // 0.51244 * 1.01 = 0.5175644, rounded up.
+        let buy = order(&market, TradeSide::Buy, &book, "0.01").unwrap();
+        assert_eq!(buy.market, "XRP-EUR");
+        assert_eq!(buy.order_type, OrderType::Limit);
+        assert!(matches!(
+            buy.time_in_force,
+            Some(TimeInForce::ImmediateOrCancel)
+        ));
+        assert_eq!(buy.amount.as_deref(), Some("10"));
+        assert_eq!(buy.price.as_deref(), Some("0.51757"));
+
+        // 0.51234 * 0.99 = 0.5072166, rounded down.
+        let sell = order(&market, TradeSide::Sell, &book, "0.01").unwrap();
+        assert_eq!(sell.price.as_deref(), Some("0.50721"));
+
+        // A coarse best price keeps the slippage: 0.5 * 1.01 = 0.505.
+        let coarse: OrderBook = serde_json::from_str(
+            r#"{"market":"XRP-EUR","nonce":1,"bids":[["0.5","10"]],"asks":[["0.5","10"]]}"#,
+        )
+        .unwrap();
+        let buy = order(&market, TradeSide::Buy, &coarse, "0.01").unwrap();
+        assert_eq!(buy.price.as_deref(), Some("0.505"));
+
+        // Six digits before the point, of which five are significant.
+        let btc_eur = btc_eur_market();
+        let btc: OrderBook = serde_json::from_str(
+            r#"{"market":"BTC-EUR","nonce":1,"bids":[["123456","1"]],"asks":[["123456","1"]]}"#,
+        )
+        .unwrap();
+        // 123456 * 1.01 = 124690.56, rounded up.
+        let buy = order(&btc_eur, TradeSide::Buy, &btc, "0.01").unwrap();
+        assert_eq!(buy.price.as_deref(), Some("124700"));
+        // 123456 * 0.99 = 122221.44, rounded down.
+        let sell = order(&btc_eur, TradeSide::Sell, &btc, "0.01").unwrap();
+        assert_eq!(sell.price.as_deref(), Some("122220"));
+
+        // A book of another market is not used to price the order.
+        let err = order(&market, TradeSide::Buy, &btc, "0.01")
+            .expect_err("Pricing from another market's book should fail");
+        assert!(matches!(
+            err,
+            crate::Error::InvalidOrder(crate::InvalidOrder::BookMarketMismatch { ref market, ref book })
+                if market == "XRP-EUR" && book == "BTC-EUR"
+        ));
+
+        let zero = order(&market, TradeSide::Buy, &book, "0").unwrap();
+        assert_eq!(zero.price.as_deref(), Some("0.51244"));
+
+        for max_slippage in ["-0.01", "1", "1.5"] {
+            assert!(matches!(
+                order(&market, TradeSide::Buy, &book, max_slippage),
+                Err(crate::Error::InvalidNumber {
+                    field: "max_slippage",
+                    ..
+                })
+            ));
+        }
+
+        let empty: OrderBook =
+            serde_json::from_str(r#"{"market":"XRP-EUR","nonce":1,"bids":[],"asks":[]}"#).unwrap();
+        assert!(matches!(
+            order(&market, TradeSide::Buy, &empty, "0.01"),
+            Err(crate::Error::InvalidNumber { field: "price", .. })
+        ));
+    }
+
+    #[test]
+    fn order_book_levels() {
+        let book: OrderBook = serde_json::from_str(
+            r#"{"market":"BTC-EUR","nonce":1,"bids":[["100","1"],["99","3"]],"asks":[["101","2"]]}"#,
+        )
+        .expect("Deserializing the order book should succeed");
+
+        assert_eq!(book.depth(), (2, 1));
+
+        let bids: Vec<_> = book
+            .levels(BookSide::Bid)
+            .map(|q| q.price.as_str())
+            .collect();
+        assert_eq!(bids, ["100", "99"]);
+
+        let asks: Vec<_> = book
+            .levels(BookSide::Ask)
+            .map(|q| q.amount.as_str())
+            .collect();
+        assert_eq!(asks, ["2"]);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn order_book_market_protection() {
+        let book: OrderBook = serde_json::from_str(
+            r#"{
+                "market": "BTC-EUR",
+                "nonce": 1,
+                "bids": [["100", "1"], ["99", "1"], ["90", "1"]],
+                "asks": [["101", "1"], ["102", "1"], ["110", "1"]]
+            }"#,
+        )
+        .expect("Deserializing the order book should succeed");
+
+        let slippage = dec("0.02");
+
+        // Within the best level, or the first two levels.
+        assert!(!book.would_trip_protection(TradeSide::Buy, dec("1"), slippage));
+        assert!(!book.would_trip_protection(TradeSide::Buy, dec("1.5"), slippage));
+        assert!(!book.would_trip_protection(TradeSide::Sell, dec("2"), slippage));
+
+        // Reaching the third level moves the price too far.
+        assert!(book.would_trip_protection(TradeSide::Buy, dec("2.5"), slippage));
+        assert!(book.would_trip_protection(TradeSide::Sell, dec("3"), slippage));
+
+        // More than the book can fill.
+        assert!(book.would_trip_protection(TradeSide::Buy, dec("4"), dec("1")));
+    }
+
+    #[test]
+    fn order_book_nonce_sequence() {
+        let book: OrderBook = serde_json::from_str(
+            r#"{"market":"BTC-EUR","nonce":41,"bids":[["100","1"]],"asks":[["101","2"]]}"#,
+        )
+        .expect("Deserializing the order book should succeed");
+
+        book.verify_nonce(42)
+            .expect("The next nonce should be accepted");
+
+        let err = book
+            .verify_nonce(44)
+            .expect_err("A gap in the nonce sequence should be rejected");
+        assert!(matches!(
+            err,
+            crate::Error::NonceMismatch {
+                expected: 42,
+                received: 44
+            }
+        ));
+
+        book.verify_nonce(41)
+            .expect_err("A repeated nonce should be rejected");
+
+        let book = OrderBook {
+            nonce: u64::MAX,
+            ..book
+        };
+        let err = book
+            .verify_nonce(0)
+            .expect_err("A nonce after the last one should be rejected");
+        assert!(matches!(
+            err,
+            crate::Error::NonceMismatch {
+                expected: u64::MAX,
+                received: 0
+            }
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn numeric_order_book() {
+        let json =
+            r#"{"market":"BTC-EUR","nonce":1,"bids":[["100.5","1.25"]],"asks":[["101","2"]]}"#;
+
+        let book: NumericOrderBook =
+            serde_json::from_str(json).expect("Deserializing the order book should succeed");
+        assert_eq!(
+            book.bids,
+            [NumericQuote {
+                price: dec("100.5"),
+                amount: dec("1.25")
+            }]
+        );
+        assert_eq!(
+            <(Decimal, Decimal)>::from(book.asks[0]),
+            (dec("101"), dec("2"))
+        );
+
+        // Deserializers that cannot lend out strings work too.
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let book: NumericOrderBook =
+            serde_json::from_value(value).expect("Deserializing the order book should succeed");
+        assert_eq!(book.bids[0].price, dec("100.5"));
+
+        serde_json::from_str::<NumericQuote>(r#"["NaN", "1"]"#)
+            .expect_err("A non-numeric price should be rejected");
+    }
+
+    #[test]
+    fn estimated_deposit_time() {
+        let asset = |symbol: &str, confirmations: u64, network: &str| -> Asset {
+            let asset = fixtures::asset(serde_json::json!({
+                "symbol": symbol,
+                "depositConfirmations": confirmations,
+                "networks": [network],
+            }));
+            serde_json::from_value(asset).expect("Deserializing the asset should succeed")
+        };
+
+        let btc = asset("BTC", 2, "Mainnet");
+        assert_eq!(
+            btc.estimated_deposit_time(),
+            Some(Duration::from_secs(20 * 60))
+        );
+
+        let usdt = asset("USDT", 64, "ERC20");
+        assert_eq!(
+            usdt.estimated_deposit_time(),
+            Some(Duration::from_secs(64 * 12))
+        );
+
+        let unknown = asset("XYZ", 10, "Mainnet");
+        assert_eq!(unknown.estimated_deposit_time(), None);
+
+        let block_times = BlockTimes::default().with("XYZ", Duration::from_secs(5));
+        assert_eq!(
+            unknown.estimated_deposit_time_with(&block_times),
+            Some(Duration::from_secs(50))
+        );
+    }
+
+    #[test]
+    fn candle_interval_duration() {
+        assert_eq!(
+            CandleInterval::OneMinute.duration(),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            CandleInterval::EightHours.duration(),
+            Duration::from_secs(8 * 3600)
+        );
+        assert_eq!(
+            CandleInterval::OneDay.duration(),
+            Duration::from_secs(86400)
+        );
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn local_day_bounds_across_dst() {
+        use chrono::NaiveDate;
+        use chrono_tz::{America, Europe, UTC};
+
+        const HOUR: u64 = 3600 * 1000;
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // 2024-01-15T00:00:00Z, the exchange's own boundary.
+        let (start, end) = local_day_bounds(UTC, date(2024, 1, 15)).unwrap();
+        assert_eq!(start, 1705276800000);
+        assert_eq!(end - start, 24 * HOUR);
+
+        // An ordinary day starts at 23:00 UTC the day before.
+        let (start, end) = local_day_bounds(Europe::Amsterdam, date(2024, 1, 15)).unwrap();
+        assert_eq!(start, 1705276800000 - HOUR);
+        assert_eq!(end - start, 24 * HOUR);
+
+        // Clocks go forward at 02:00, making the day an hour shorter.
+        let (start, end) = local_day_bounds(Europe::Amsterdam, date(2024, 3, 31)).unwrap();
+        assert_eq!(start, 1711839600000);
+        assert_eq!(end - start, 23 * HOUR);
+
+        // Clocks go back at 03:00, making the day an hour longer.
+        let (start, end) = local_day_bounds(Europe::Amsterdam, date(2024, 10, 27)).unwrap();
+        assert_eq!(start, 1729980000000);
+        assert_eq!(end - start, 25 * HOUR);
+
+        // The next day is back to 24 hours, starting where the previous one ended.
+        let (next_start, next_end) =
+            local_day_bounds(Europe::Amsterdam, date(2024, 10, 28)).unwrap();
+        assert_eq!(next_start, end);
+        assert_eq!(next_end - next_start, 24 * HOUR);
+
+        // Clocks go forward at midnight, so the day starts at 01:00 local time, 04:00 UTC.
+        let (start, end) = local_day_bounds(America::Santiago, date(2022, 9, 11)).unwrap();
+        assert_eq!(start, 1662868800000);
+        assert_eq!(end - start, 23 * HOUR);
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn local_day_bounds_out_of_range() {
+        use chrono::NaiveDate;
+        use chrono_tz::{Europe, UTC};
+
+        // The day before the epoch starts before it.
+        let date = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+        assert_eq!(local_day_bounds(UTC, date), None);
+
+        // Amsterdam was an hour ahead of UTC, so even the first day of 1970 started before it.
+        let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        assert_eq!(local_day_bounds(UTC, date), Some((0, 24 * 3600 * 1000)));
+        assert_eq!(local_day_bounds(Europe::Amsterdam, date), None);
+
+        assert_eq!(local_day_bounds(UTC, NaiveDate::MAX), None);
+    }
 }